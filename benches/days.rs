@@ -0,0 +1,23 @@
+// `cargo bench` runs `aoc bench` on the same solver registry the runner uses. Days can be
+// filtered: `cargo bench -- 15 3..=9`
+use std::{env, process};
+
+use advent_of_code_2022::{
+    bench,
+    cli::{Command, USAGE},
+};
+
+fn main() {
+    // Cargo adds `--bench` to the arguments of benchmarks without a harness
+    let args = env::args().skip(1).filter(|arg| arg != "--bench");
+
+    match Command::from_args(["bench".to_string()].into_iter().chain(args)) {
+        Ok(Command::Bench { days }) => process::exit(bench::run(&days)),
+        // `bench` only parses to something else for `--help`
+        Ok(_) => println!("{USAGE}"),
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    runner::{solve_day, Day, DayError, Part, Solved},
+    utils::{
+        color::{paint, Color},
+        input::{InputSource, CRATE_ROOT},
+    },
+};
+
+// Every day runs until it adds up to this time, or up to MAX_RUNS times, and keeps its best
+// timings. Slow days run just once.
+const BENCH_TIME: Duration = Duration::from_secs(1);
+const MAX_RUNS: usize = 100;
+
+// Changes of the total time of a day smaller than this are considered noise
+const THRESHOLD: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Timing {
+    parse: Duration,
+    parts: [Duration; 2],
+}
+
+impl Timing {
+    fn from_solved(solved: &Solved) -> Self {
+        Self {
+            parse: solved.parse_time,
+            parts: [solved.part_times[0], solved.part_times[1]],
+        }
+    }
+
+    fn total(&self) -> Duration {
+        self.parse + self.parts[0] + self.parts[1]
+    }
+
+    fn best(self, other: Self) -> Self {
+        Self {
+            parse: self.parse.min(other.parse),
+            parts: [
+                self.parts[0].min(other.parts[0]),
+                self.parts[1].min(other.parts[1]),
+            ],
+        }
+    }
+}
+
+fn measure(day: &Day) -> Result<Timing, DayError> {
+    let start = Instant::now();
+    let mut best = Timing::from_solved(&solve_day(day, &Part::ALL, &InputSource::Default)?);
+
+    for _ in 1..MAX_RUNS {
+        if start.elapsed() >= BENCH_TIME {
+            break;
+        }
+
+        let solved = solve_day(day, &Part::ALL, &InputSource::Default)?;
+        best = best.best(Timing::from_solved(&solved));
+    }
+
+    Ok(best)
+}
+
+// The timings of every benchmark run, one line per day:
+//
+//     <commit>\t<day>\t<parse ns>\t<part 1 ns>\t<part 2 ns>
+//
+// It lives in `target/`, timings only make sense on the machine that took them.
+fn history_path() -> PathBuf {
+    Path::new(CRATE_ROOT)
+        .join("target")
+        .join("bench-history.tsv")
+}
+
+fn read_history(path: &Path) -> io::Result<Vec<(String, u8, Timing)>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err),
+    };
+
+    // Lines that don't parse, from a run that was interrupted for example, are skipped
+    let entries = content
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let [commit, day, parse, part1, part2] = fields[..] else {
+                return None;
+            };
+            let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+
+            Some((
+                commit.to_string(),
+                day.parse().ok()?,
+                Timing {
+                    parse: nanos(parse)?,
+                    parts: [nanos(part1)?, nanos(part2)?],
+                },
+            ))
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+// The latest timings of the last commit benchmarked before `current`
+fn baseline(
+    history: &[(String, u8, Timing)],
+    current: &str,
+) -> Option<(String, HashMap<u8, Timing>)> {
+    let (commit, _, _) = history
+        .iter()
+        .rev()
+        .find(|(commit, _, _)| commit != current)?;
+
+    let timings = history
+        .iter()
+        .filter(|(other, _, _)| other == commit)
+        .map(|(_, day, timing)| (*day, *timing))
+        .collect();
+
+    Some((commit.clone(), timings))
+}
+
+// The short hash of HEAD, marked as dirty when there are uncommitted changes
+fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(CRATE_ROOT)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(changes) if changes.is_empty() => hash,
+            _ => format!("{hash}-dirty"),
+        },
+        None => String::from("unknown"),
+    }
+}
+
+fn format_change(current: Duration, baseline: Option<&Timing>) -> String {
+    let Some(baseline) = baseline else {
+        return format!("{:>8}", "new");
+    };
+
+    let change = current.as_secs_f64() / baseline.total().as_secs_f64() - 1.0;
+    let label = format!("{:>+7.1}%", change * 100.0);
+
+    if change > THRESHOLD {
+        paint(&label, Color::Red)
+    } else if change < -THRESHOLD {
+        paint(&label, Color::Green)
+    } else {
+        label
+    }
+}
+
+// Runs `aoc bench`: times the days, prints how they compare to the last benchmarked commit and
+// adds the timings to the history. Returns the number of days that failed.
+pub fn bench(days: &[&Day]) -> io::Result<usize> {
+    let path = history_path();
+    let commit = current_commit();
+    let history = read_history(&path)?;
+    let baseline = baseline(&history, &commit);
+
+    match &baseline {
+        Some((base, _)) => println!("Benchmarking {commit} against {base}\n"),
+        None => println!("Benchmarking {commit}, there is no previous run to compare with\n"),
+    }
+
+    println!(
+        "Day  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "Parse", "Part 1", "Part 2", "Total", "Change"
+    );
+
+    let mut results = vec![];
+    let mut failures = 0;
+    let mut regressions = 0;
+
+    for day in days {
+        let timing = match measure(day) {
+            Ok(timing) => timing,
+            Err(err) => {
+                println!("{:>3}  {err}", day.number);
+                failures += 1;
+                continue;
+            }
+        };
+
+        let base = baseline
+            .as_ref()
+            .and_then(|(_, timings)| timings.get(&day.number));
+        if base.is_some_and(|base| {
+            timing.total().as_secs_f64() > base.total().as_secs_f64() * (1.0 + THRESHOLD)
+        }) {
+            regressions += 1;
+        }
+
+        let [parse, part1, part2, total] = [
+            timing.parse,
+            timing.parts[0],
+            timing.parts[1],
+            timing.total(),
+        ]
+        .map(|duration| format!("{duration:.2?}"));
+
+        println!(
+            "{:>3}  {parse:>10}  {part1:>10}  {part2:>10}  {total:>10}  {}",
+            day.number,
+            format_change(timing.total(), base)
+        );
+        results.push((day.number, timing));
+    }
+
+    if regressions > 0 {
+        println!(
+            "\n{regressions} day(s) got more than {:.0}% slower",
+            THRESHOLD * 100.0
+        );
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    for (day, timing) in results {
+        writeln!(
+            file,
+            "{commit}\t{day}\t{}\t{}\t{}",
+            timing.parse.as_nanos(),
+            timing.parts[0].as_nanos(),
+            timing.parts[1].as_nanos()
+        )?;
+    }
+
+    Ok(failures)
+}
+
+// Runs `bench` for `aoc bench` and `cargo bench`, reporting failures, and returns the exit
+// code
+pub fn run(days: &[&Day]) -> i32 {
+    match bench(days) {
+        Ok(0) => 0,
+        Ok(failures) => {
+            eprintln!("\n{failures} day(s) failed");
+            1
+        }
+        Err(err) => {
+            eprintln!("Can't update the benchmark history: {err}");
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(total_ms: u64) -> Timing {
+        Timing {
+            parse: Duration::ZERO,
+            parts: [Duration::from_millis(total_ms), Duration::ZERO],
+        }
+    }
+
+    #[test]
+    fn baseline_is_the_last_other_commit() {
+        let history = vec![
+            (String::from("aaa"), 1, timing(10)),
+            (String::from("bbb"), 1, timing(20)),
+            (String::from("bbb"), 2, timing(30)),
+            (String::from("ccc"), 1, timing(40)),
+        ];
+
+        let (commit, timings) = baseline(&history, "ccc").unwrap();
+
+        assert_eq!(commit, "bbb");
+        assert_eq!(timings.get(&1), Some(&timing(20)));
+        assert_eq!(timings.get(&2), Some(&timing(30)));
+        assert!(baseline(&history[..1], "aaa").is_none());
+    }
+
+    #[test]
+    fn changes_are_relative_to_the_baseline() {
+        assert_eq!(
+            format_change(Duration::from_millis(15), Some(&timing(10))),
+            "  +50.0%"
+        );
+        assert_eq!(
+            format_change(Duration::from_millis(5), Some(&timing(10))),
+            "  -50.0%"
+        );
+        assert_eq!(format_change(Duration::from_millis(5), None), "     new");
+    }
+}
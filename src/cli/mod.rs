@@ -0,0 +1,426 @@
+use std::fmt::Display;
+
+use crate::{
+    runner::{find_day, Day, Format, Part, RunOptions, DAYS},
+    utils::input::InputSource,
+};
+
+pub const USAGE: &str = "\
+Usage:
+    aoc [run] [DAYS...] [--part <1|2>] [--input <FILE>] [--time] [--format <text|json>]
+        [--jobs <N>]
+    aoc verify [DAYS...] [--record]
+    aoc bench [DAYS...]
+    aoc list
+    aoc help
+
+DAYS can be a single day (12), a range (3..9) or an inclusive range (3..=9).
+Without DAYS every day is run.
+
+Inputs are read from `inputs/day_N.txt`, relative to the working directory or to the
+crate root, or from the directory in $AOC_INPUT_DIR when it is set.
+--input reads the input of a single day from FILE, or from stdin when FILE is `-`.
+--time prints how long parsing and each part took.
+--format json prints one object per part, with its answer, timings and status.
+--jobs solves the parts on N threads, the results are still printed in day order.
+
+verify compares the answers with the ones in `answers.toml`, and --record writes the
+current answers into it.
+
+bench times the days and compares them with the last benchmarked commit. It's also run by
+`cargo bench`.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        days: Vec<&'static Day>,
+        options: RunOptions,
+    },
+    Verify {
+        days: Vec<&'static Day>,
+        record: bool,
+    },
+    Bench {
+        days: Vec<&'static Day>,
+    },
+    List,
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliError(String);
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Command {
+    pub fn from_args<I>(args: I) -> Result<Self, CliError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter().peekable();
+
+        match args.peek().map(String::as_str) {
+            Some("list") => {
+                args.next();
+                match args.next() {
+                    Some(extra) => Err(CliError(format!("Unexpected argument: {extra}"))),
+                    None => Ok(Command::List),
+                }
+            }
+            Some("help" | "-h" | "--help") => Ok(Command::Help),
+            Some("run") => {
+                args.next();
+                Self::parse_run(args)
+            }
+            Some("verify") => {
+                args.next();
+                Self::parse_verify(args)
+            }
+            Some("bench") => {
+                args.next();
+                Self::parse_bench(args)
+            }
+            _ => Self::parse_run(args),
+        }
+    }
+
+    fn parse_run(args: impl Iterator<Item = String>) -> Result<Self, CliError> {
+        let mut days = vec![];
+        let mut options = RunOptions::default();
+        let mut args = args;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" | "-p" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| CliError(format!("Missing value for {arg}")))?;
+                    options.parts = vec![parse_part(&value)?];
+                }
+                "--input" | "-i" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| CliError(format!("Missing value for {arg}")))?;
+                    options.input = InputSource::from(value.as_str());
+                }
+                "--format" | "-f" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| CliError(format!("Missing value for {arg}")))?;
+                    options.format = parse_format(&value)?;
+                }
+                "--jobs" | "-j" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| CliError(format!("Missing value for {arg}")))?;
+                    options.jobs = parse_jobs(&value)?;
+                }
+                "--time" | "-t" => options.time = true,
+                "-h" | "--help" => return Ok(Command::Help),
+                flag if flag.starts_with('-') => {
+                    return Err(CliError(format!("Unknown option: {flag}")));
+                }
+                spec => days.extend(parse_days(spec)?),
+            }
+        }
+
+        let days = all_days_when_empty(days);
+
+        if options.input != InputSource::Default && days.len() != 1 {
+            return Err(CliError(String::from(
+                "--input can only be used when running a single day",
+            )));
+        }
+
+        Ok(Command::Run { days, options })
+    }
+
+    fn parse_verify(args: impl Iterator<Item = String>) -> Result<Self, CliError> {
+        let mut days = vec![];
+        let mut record = false;
+
+        for arg in args {
+            match arg.as_str() {
+                "--record" => record = true,
+                "-h" | "--help" => return Ok(Command::Help),
+                flag if flag.starts_with('-') => {
+                    return Err(CliError(format!("Unknown option: {flag}")));
+                }
+                spec => days.extend(parse_days(spec)?),
+            }
+        }
+
+        Ok(Command::Verify {
+            days: all_days_when_empty(days),
+            record,
+        })
+    }
+
+    fn parse_bench(args: impl Iterator<Item = String>) -> Result<Self, CliError> {
+        let mut days = vec![];
+
+        for arg in args {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Command::Help),
+                flag if flag.starts_with('-') => {
+                    return Err(CliError(format!("Unknown option: {flag}")));
+                }
+                spec => days.extend(parse_days(spec)?),
+            }
+        }
+
+        Ok(Command::Bench {
+            days: all_days_when_empty(days),
+        })
+    }
+}
+
+// Sorts and dedups the selected days, or selects every day when there are none
+fn all_days_when_empty(mut days: Vec<&'static Day>) -> Vec<&'static Day> {
+    if days.is_empty() {
+        days = DAYS.iter().collect();
+    }
+
+    days.sort_by_key(|day| day.number);
+    days.dedup();
+    days
+}
+
+fn parse_part(s: &str) -> Result<Part, CliError> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(CliError(format!("Invalid part: {s} (expected 1 or 2)"))),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format, CliError> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(CliError(format!(
+            "Invalid format: {s} (expected text or json)"
+        ))),
+    }
+}
+
+fn parse_jobs(s: &str) -> Result<usize, CliError> {
+    match s.parse() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(CliError(format!(
+            "Invalid number of jobs: {s} (expected at least 1)"
+        ))),
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8, CliError> {
+    let day = s
+        .parse::<u8>()
+        .map_err(|_| CliError(format!("Invalid day: {s}")))?;
+
+    match find_day(day) {
+        Some(_) => Ok(day),
+        None => Err(CliError(format!("Day {day} is not solved"))),
+    }
+}
+
+fn parse_days(s: &str) -> Result<Vec<&'static Day>, CliError> {
+    let numbers = if let Some((start, end)) = s.split_once("..=") {
+        let (start, end) = (parse_day(start)?, parse_day(end)?);
+        if start > end {
+            return Err(CliError(format!("Empty range of days: {s}")));
+        }
+        (start..=end).collect()
+    } else if let Some((start, end)) = s.split_once("..") {
+        let (start, end) = (parse_day(start)?, parse_day(end)?);
+        if start >= end {
+            return Err(CliError(format!("Empty range of days: {s}")));
+        }
+        (start..end).collect()
+    } else {
+        vec![parse_day(s)?]
+    };
+
+    // Days in a range between two solved days are solved too, the registry has no gaps
+    Ok(numbers.into_iter().filter_map(find_day).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, CliError> {
+        Command::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn days(numbers: &[u8]) -> Vec<&'static Day> {
+        numbers
+            .iter()
+            .map(|number| find_day(*number).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn no_args_runs_everything() {
+        let Ok(Command::Run { days, options }) = parse(&[]) else {
+            panic!("Expected a run command");
+        };
+
+        assert_eq!(days, DAYS.iter().collect::<Vec<_>>());
+        assert_eq!(options, RunOptions::default());
+    }
+
+    #[test]
+    fn run_single_day_and_part() {
+        assert_eq!(
+            parse(&["run", "12", "--part", "2"]),
+            Ok(Command::Run {
+                days: days(&[12]),
+                options: RunOptions {
+                    parts: vec![Part::Two],
+                    ..Default::default()
+                },
+            })
+        );
+        assert_eq!(
+            parse(&["1", "--time"]),
+            Ok(Command::Run {
+                days: days(&[1]),
+                options: RunOptions {
+                    time: true,
+                    ..Default::default()
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn run_ranges() {
+        assert_eq!(
+            parse(&["run", "3..6"]),
+            Ok(Command::Run {
+                days: days(&[3, 4, 5]),
+                options: RunOptions {
+                    ..Default::default()
+                },
+            })
+        );
+        assert_eq!(
+            parse(&["run", "3..=6", "1"]),
+            Ok(Command::Run {
+                days: days(&[1, 3, 4, 5, 6]),
+                options: RunOptions {
+                    ..Default::default()
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn run_with_input() {
+        assert_eq!(
+            parse(&["run", "5", "--input", "-"]),
+            Ok(Command::Run {
+                days: days(&[5]),
+                options: RunOptions {
+                    input: InputSource::Stdin,
+                    ..Default::default()
+                },
+            })
+        );
+        assert_eq!(
+            parse(&["5", "-i", "example.txt"]),
+            Ok(Command::Run {
+                days: days(&[5]),
+                options: RunOptions {
+                    input: InputSource::from("example.txt"),
+                    ..Default::default()
+                },
+            })
+        );
+        assert!(parse(&["run", "5..7", "--input", "example.txt"]).is_err());
+    }
+
+    #[test]
+    fn run_with_format() {
+        assert_eq!(
+            parse(&["run", "5", "--format", "json"]),
+            Ok(Command::Run {
+                days: days(&[5]),
+                options: RunOptions {
+                    format: Format::Json,
+                    ..Default::default()
+                },
+            })
+        );
+        assert!(parse(&["run", "5", "--format", "yaml"]).is_err());
+    }
+
+    #[test]
+    fn run_with_jobs() {
+        assert_eq!(
+            parse(&["run", "--jobs", "8"]),
+            Ok(Command::Run {
+                days: DAYS.iter().collect(),
+                options: RunOptions {
+                    jobs: 8,
+                    ..Default::default()
+                },
+            })
+        );
+        assert!(parse(&["run", "-j", "0"]).is_err());
+        assert!(parse(&["run", "-j", "many"]).is_err());
+    }
+
+    #[test]
+    fn verify() {
+        assert_eq!(
+            parse(&["verify"]),
+            Ok(Command::Verify {
+                days: DAYS.iter().collect(),
+                record: false,
+            })
+        );
+        assert_eq!(
+            parse(&["verify", "--record", "12"]),
+            Ok(Command::Verify {
+                days: days(&[12]),
+                record: true,
+            })
+        );
+        assert!(parse(&["verify", "--part", "1"]).is_err());
+    }
+
+    #[test]
+    fn bench() {
+        assert_eq!(
+            parse(&["bench", "15", "3..=4"]),
+            Ok(Command::Bench {
+                days: days(&[3, 4, 15])
+            })
+        );
+        assert!(parse(&["bench", "--record"]).is_err());
+    }
+
+    #[test]
+    fn list_and_help() {
+        assert_eq!(parse(&["list"]), Ok(Command::List));
+        assert_eq!(parse(&["--help"]), Ok(Command::Help));
+        assert_eq!(parse(&["run", "-h"]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn invalid_args() {
+        assert!(parse(&["run", "30"]).is_err());
+        assert!(parse(&["run", "9..3"]).is_err());
+        assert!(parse(&["run", "9..=3"]).is_err());
+        assert!(parse(&["run", "1", "--part", "3"]).is_err());
+        assert!(parse(&["run", "1", "--part"]).is_err());
+        assert!(parse(&["run", "--fast"]).is_err());
+        assert!(parse(&["list", "1"]).is_err());
+    }
+}
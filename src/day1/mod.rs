@@ -1,44 +1,61 @@
-use crate::utils::get_input_content;
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    // Calories carried by each elf
+    type Parsed = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut calories: Vec<i32> = Vec::new();
+
+        let last = input.lines().try_fold(0, |acc, line| {
+            if line.is_empty() {
+                calories.push(acc);
+                Ok(0)
+            } else {
+                let calories = parse_number::<i32>(line)?;
+                Ok::<_, ParseError>(acc + calories)
+            }
+        })?;
+        calories.push(last);
 
-const INPUT_PATH: &str = "inputs/day_1.txt";
+        Ok(calories)
+    }
 
-pub fn task_1() {
-    let mut most_calories = 0;
-    let mut current_count = 0;
+    fn part1(calories: &Self::Parsed) -> Answer {
+        let most_calories = calories.iter().max().copied().unwrap_or_default();
+        most_calories.into()
+    }
 
-    let file_contents = get_input_content(INPUT_PATH);
+    fn part2(calories: &Self::Parsed) -> Answer {
+        let mut calories = calories.clone();
 
-    file_contents.lines().for_each(|line| {
-        if line.is_empty() {
-            if current_count > most_calories {
-                most_calories = current_count;
-            }
-            current_count = 0;
-        } else {
-            let calories = line.parse::<i32>().unwrap();
-            current_count += calories;
-        }
-    });
-
-    println!("Most calories: {}", most_calories);
+        calories.sort();
+        let result: i32 = calories.iter().rev().take(3).sum();
+
+        result.into()
+    }
 }
 
-pub fn task_2() {
-    let mut calories: Vec<i32> = Vec::new();
-    let file_contents = get_input_content(INPUT_PATH);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    file_contents.lines().fold(0, |acc, line| {
-        if line.is_empty() {
-            calories.push(acc);
-            0
-        } else {
-            let calories = line.parse::<i32>().unwrap();
-            acc + calories
-        }
-    });
+    const EXAMPLE: &str = include_str!("example.txt");
 
-    calories.sort();
-    let result: i32 = calories.iter().rev().take(3).sum();
+    #[test]
+    fn part1_example() {
+        let parsed = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part1(&parsed), Answer::Int(24000));
+    }
 
-    println!("Calories: {}", result);
+    #[test]
+    fn part2_example() {
+        let parsed = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part2(&parsed), Answer::Int(45000));
+    }
 }
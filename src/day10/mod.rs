@@ -1,133 +1,298 @@
-use std::{collections::VecDeque, num::ParseIntError, str::FromStr};
+use std::str::FromStr;
 
-use crate::utils::get_input_content;
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+    utils::grid::Grid,
+};
 
-const INPUT_PATH: &str = "inputs/day_10.txt";
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    pub x: i32,
+}
 
+impl Default for Registers {
+    fn default() -> Self {
+        Self { x: 1 }
+    }
+}
+
+// An instruction of the CPU: its mnemonic, whether it takes a value, how many cycles it lasts
+// and what it does to the registers once they are over
 #[derive(Debug)]
-enum Inst {
-    Noop,
-    Addx(i32),
+pub struct InstSpec {
+    pub mnemonic: &'static str,
+    pub operand: bool,
+    pub cycles: usize,
+    pub effect: fn(&mut Registers, i32),
+}
+
+pub const INSTRUCTIONS: &[InstSpec] = &[
+    InstSpec {
+        mnemonic: "noop",
+        operand: false,
+        cycles: 1,
+        effect: |_, _| {},
+    },
+    InstSpec {
+        mnemonic: "addx",
+        operand: true,
+        cycles: 2,
+        effect: |registers, value| registers.x += value,
+    },
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Inst {
+    spec: &'static InstSpec,
+    operand: i32,
 }
 
 impl FromStr for Inst {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split_whitespace();
 
-        let _inst = split.next().expect("Should have a instruction");
+        let mnemonic = split
+            .next()
+            .ok_or_else(|| ParseError::new("Expected an instruction", s))?;
+        let spec = INSTRUCTIONS
+            .iter()
+            .find(|spec| spec.mnemonic == mnemonic)
+            .ok_or_else(|| ParseError::new("Unknown instruction", mnemonic))?;
+
+        let operand = match spec.operand {
+            true => parse_number(
+                split
+                    .next()
+                    .ok_or_else(|| ParseError::new("Missing the value", s))?,
+            )?,
+            false => 0,
+        };
 
         match split.next() {
-            Some(amount) => Ok(Inst::Addx(amount.parse::<i32>()?)),
-            None => Ok(Inst::Noop),
+            Some(extra) => Err(ParseError::new("Unexpected text", extra)),
+            None => Ok(Self { spec, operand }),
         }
     }
 }
 
-#[derive(Debug)]
-struct Program {
-    insts: VecDeque<Inst>,
-    x_values: Vec<i32>,
+#[derive(Debug, Clone)]
+pub struct Program {
+    insts: Vec<Inst>,
 }
 
-impl Program {
-    pub fn execute(&mut self) {
-        let mut x = 1;
-        let mut on_hold = None;
+impl FromStr for Program {
+    type Err = ParseError;
 
-        loop {
-            self.x_values.push(x);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let insts = s
+            .lines()
+            .map(|l| l.parse::<Inst>())
+            .collect::<Result<_, _>>()
+            .map_err(|err| err.locate(s))?;
 
-            if on_hold.is_some() {
-                let to_change = on_hold.unwrap();
-                x += to_change;
+        Ok(Self { insts })
+    }
+}
 
-                on_hold = None;
-                continue;
-            }
+// Something looking at the CPU, called for every cycle with its number, from 1, and the
+// registers during it
+pub trait Observer {
+    fn on_cycle(&mut self, cycle: usize, registers: &Registers);
+}
+
+impl<F: FnMut(usize, &Registers)> Observer for F {
+    fn on_cycle(&mut self, cycle: usize, registers: &Registers) {
+        self(cycle, registers)
+    }
+}
 
-            let next_inst = self.insts.pop_front();
-            match next_inst {
-                Some(inst) => match inst {
-                    Inst::Noop => continue,
-                    Inst::Addx(temp) => on_hold = Some(temp),
-                },
-                None => break,
+#[derive(Default)]
+pub struct Cpu<'a> {
+    registers: Registers,
+    // Cycles done so far
+    cycles: usize,
+    observers: Vec<&'a mut dyn Observer>,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn observe(&mut self, observer: &'a mut dyn Observer) -> &mut Self {
+        self.observers.push(observer);
+        self
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn cycles(&self) -> usize {
+        self.cycles
+    }
+
+    // Runs `program` from where the CPU is, cycle after cycle. Running another program
+    // carries on with the registers and the cycle count.
+    pub fn run(&mut self, program: &Program) {
+        for inst in &program.insts {
+            for _ in 0..inst.spec.cycles {
+                self.cycles += 1;
+                for observer in self.observers.iter_mut() {
+                    observer.on_cycle(self.cycles, &self.registers);
+                }
             }
+
+            (inst.spec.effect)(&mut self.registers, inst.operand);
         }
     }
 }
 
-impl FromStr for Program {
-    type Err = ParseIntError;
+// Sums the signal strengths, cycle times X, of the 20th cycle and every 40 cycles after it
+// until the 220th
+#[derive(Debug, Default)]
+pub struct SignalStrength {
+    pub total: i64,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            insts: s.lines().map(|l| l.parse::<Inst>().unwrap()).collect(),
-            x_values: vec![],
-        })
+impl Observer for SignalStrength {
+    fn on_cycle(&mut self, cycle: usize, registers: &Registers) {
+        if cycle <= 220 && cycle % 40 == 20 {
+            self.total += cycle as i64 * i64::from(registers.x);
+        }
     }
 }
 
-pub fn task_1() {
-    let input = get_input_content(INPUT_PATH);
-    let mut p: Program = input.parse().unwrap();
+// Draws a pixel per cycle, lit when the 3 pixels wide sprite centred on X covers it
+#[derive(Debug)]
+pub struct Crt {
+    pub screen: Grid<char>,
+}
 
-    p.execute();
+impl Default for Crt {
+    fn default() -> Self {
+        Self {
+            screen: Grid::new(40, 6, '.'),
+        }
+    }
+}
 
-    let sum: i32 = p
-        .x_values
-        .iter()
-        .enumerate()
-        .fold(0, |mut acc, (idx, i)| match idx + 1 {
-            factor @ (20 | 60 | 100 | 140 | 180 | 220) => {
-                acc += factor as i32 * *i;
-                acc
+impl Observer for Crt {
+    fn on_cycle(&mut self, cycle: usize, registers: &Registers) {
+        let pixel = cycle - 1;
+        let pos = (pixel % self.screen.width(), pixel / self.screen.width());
+
+        if let Some(cell) = self.screen.get_mut(pos) {
+            if (pos.0 as i32 - registers.x).abs() <= 1 {
+                *cell = '#';
             }
-            _ => acc,
-        });
-
-    println!("Sum of signal strengths: {}", sum);
-}
-
-pub fn task_2() {
-    let input = get_input_content(INPUT_PATH);
-    let mut p: Program = input.parse().unwrap();
-
-    p.execute();
-
-    let screen = p
-        .x_values
-        .chunks(40)
-        .take(6)
-        .map(|chunk| {
-            let mut sprite_pos = 0..=2;
-
-            chunk
-                .iter()
-                .enumerate()
-                .fold(String::new(), |mut acc, (idx, i)| {
-                    if *i - 1 < 0 {
-                        sprite_pos = 0..=2;
-                    } else {
-                        sprite_pos = (*i as usize) - 1..=(*i as usize) + 1;
-                    }
-
-                    if sprite_pos.contains(&idx) {
-                        acc.push('#');
-                    } else {
-                        acc.push('.');
-                    }
-
-                    acc
-                })
-        })
-        .fold(String::new(), |mut acc, s| {
-            acc.push_str(&s);
-            acc.push('\n');
-            acc
-        });
-
-    println!("CTR display:\n{}", screen); // Uppercase letters are: FBURHZCH
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Program;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(p: &Self::Parsed) -> Answer {
+        let mut signal = SignalStrength::default();
+        Cpu::default().observe(&mut signal).run(p);
+
+        signal.total.into()
+    }
+
+    fn part2(p: &Self::Parsed) -> Answer {
+        let mut crt = Crt::default();
+        Cpu::default().observe(&mut crt).run(p);
+
+        Answer::Grid(crt.screen.rows().map(String::from_iter).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part1(&parsed), Answer::Int(13140));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day10::parse(EXAMPLE).unwrap();
+        let screen = [
+            "##..##..##..##..##..##..##..##..##..##..",
+            "###...###...###...###...###...###...###.",
+            "####....####....####....####....####....",
+            "#####.....#####.....#####.....#####.....",
+            "######......######......######......####",
+            "#######.......#######.......#######.....",
+        ];
+
+        assert_eq!(
+            Day10::part2(&parsed),
+            Answer::Grid(screen.map(String::from).to_vec())
+        );
+    }
+
+    #[test]
+    fn observers_see_every_cycle() {
+        let program = "noop\naddx 3\naddx -5".parse::<Program>().unwrap();
+        let mut values = vec![];
+        let mut record = |cycle: usize, registers: &Registers| values.push((cycle, registers.x));
+
+        let mut cpu = Cpu::default();
+        cpu.observe(&mut record).run(&program);
+        assert_eq!((cpu.registers().x, cpu.cycles()), (-1, 5));
+
+        // Another run carries on from there
+        cpu.run(&"addx 2".parse().unwrap());
+        assert_eq!((cpu.registers().x, cpu.cycles()), (1, 7));
+        drop(cpu);
+
+        assert_eq!(
+            values,
+            vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4), (6, -1), (7, -1)]
+        );
+    }
+
+    #[test]
+    fn crt_sprite_can_be_off_screen() {
+        let mut crt = Crt::default();
+
+        for cycle in 1..=3 {
+            crt.on_cycle(cycle, &Registers { x: -1 });
+        }
+        crt.on_cycle(41, &Registers { x: 0 });
+        crt.on_cycle(43, &Registers { x: 0 });
+
+        assert_eq!(crt.screen.row(0)[..3], ['#', '.', '.']);
+        assert_eq!(crt.screen.row(1)[..3], ['#', '.', '.']);
+    }
+
+    #[test]
+    fn rejects_unknown_instructions() {
+        let error = |s: &str| s.parse::<Program>().unwrap_err().to_string();
+
+        assert_eq!(
+            error("noop\nmulx 3"),
+            "line 2, column 1: Unknown instruction (found `mulx`)"
+        );
+        assert_eq!(
+            error("addx"),
+            "line 1, column 1: Missing the value (found `addx`)"
+        );
+        assert_eq!(
+            error("noop\nnoop 3"),
+            "line 2, column 6: Unexpected text (found `3`)"
+        );
+    }
 }
@@ -1,120 +1,89 @@
 use std::str::FromStr;
 
-use crate::utils::get_input_content;
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+    utils::parser::{PResult, Parser},
+};
+
+#[derive(Clone)]
+pub enum Operation {
+    Add(u64),
+    Mul(u64),
+    Square,
+}
 
-const INPUT_PATH: &str = "inputs/day_11.txt";
+impl Operation {
+    fn apply(&self, x: u64) -> u64 {
+        match self {
+            Operation::Add(n) => x + n,
+            Operation::Mul(n) => x * n,
+            Operation::Square => x * x,
+        }
+    }
+}
 
-struct Monkey {
+#[derive(Clone)]
+pub struct Monkey {
     no_items_inspected: u64,
     items: Vec<u64>,
-    op: Box<dyn Fn(u64) -> u64>,
-    test: Box<dyn Fn(u64) -> usize>,
+    op: Operation,
     test_no: u64,
+    monkey_when_true: usize,
+    monkey_when_false: usize,
 }
 
-impl FromStr for Monkey {
-    type Err = ();
+impl Monkey {
+    fn test(&self, x: u64) -> usize {
+        if x.is_multiple_of(self.test_no) {
+            self.monkey_when_true
+        } else {
+            self.monkey_when_false
+        }
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-
-        let items: Vec<u64> = lines
-            .next()
-            .unwrap()
-            .split(':')
-            .nth(1)
-            .unwrap()
-            .split(',')
-            .map(|item| item.trim().parse::<u64>().unwrap())
-            .collect();
-
-        let mut op = lines
-            .next()
-            .unwrap()
-            .split(':')
-            .nth(1)
-            .unwrap()
-            .split('=')
-            .nth(1)
-            .unwrap()
-            .split_whitespace();
-
-        let operator = op.nth(1).unwrap();
-        let op: Box<dyn Fn(u64) -> u64> = match operator {
-            "*" => {
-                let n = op.next().unwrap().trim();
-                match n {
-                    "old" => Box::new(move |x: u64| x * x),
-                    _ => {
-                        let n = n.parse::<u64>().unwrap();
-                        Box::new(move |x: u64| x * n)
-                    }
-                }
-            }
-            "+" => {
-                let n = op.next().unwrap().trim().parse::<u64>().unwrap();
-                Box::new(move |x: u64| x + n)
-            }
-            _ => panic!("Error"),
-        };
-
-        let no_to_test = lines
-            .next()
-            .unwrap()
-            .split("by ")
-            .nth(1)
-            .unwrap()
-            .trim()
-            .parse::<u64>()
-            .unwrap();
-
-        let monkey_when_true = lines
-            .next()
-            .unwrap()
-            .split("monkey ")
-            .nth(1)
-            .unwrap()
-            .trim()
-            .parse::<u64>()
-            .unwrap();
-
-        let monkey_when_false = lines
-            .next()
-            .unwrap()
-            .split("monkey ")
-            .nth(1)
-            .unwrap()
-            .trim()
-            .parse::<u64>()
-            .unwrap();
-
-        let test = move |x: u64| {
-            if x % no_to_test == 0 {
-                monkey_when_true as usize
-            } else {
-                monkey_when_false as usize
-            }
-        };
+impl Operation {
+    // Parses what follows `new = old`, like `* 19`, `+ 3` or `* old`
+    fn parse(p: &mut Parser) -> PResult<Self> {
+        let op = p.one_of(&[("*", '*'), ("+", '+')])?;
+
+        match (op, p.eat("old")) {
+            ('*', true) => Ok(Operation::Square),
+            ('*', false) => Ok(Operation::Mul(p.unsigned()?)),
+            (_, true) => Err(p.error("Only `* old` can use the old value twice")),
+            (_, false) => Ok(Operation::Add(p.unsigned()?)),
+        }
+    }
+}
 
-        lines.next();
+impl FromStr for Monkey {
+    type Err = ParseError;
 
-        Ok(Self {
-            no_items_inspected: 0,
-            items,
-            op,
-            test: Box::new(test),
-            test_no: no_to_test,
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::parse_all(s, |p| {
+            p.field("Monkey", Parser::unsigned::<usize>)?;
+            p.literal(":")?;
+
+            Ok(Self {
+                no_items_inspected: 0,
+                items: p.field("Starting items:", |p| p.list(",", Parser::unsigned))?,
+                op: p.field("Operation: new = old", Operation::parse)?,
+                test_no: p.field("Test: divisible by", Parser::unsigned)?,
+                monkey_when_true: p.field("If true: throw to monkey", Parser::unsigned)?,
+                monkey_when_false: p.field("If false: throw to monkey", Parser::unsigned)?,
+            })
         })
     }
 }
 
-struct Challenge {
+#[derive(Clone)]
+pub struct Challenge {
     monkeys: Vec<Monkey>,
-    worry: Box<dyn Fn(u64) -> u64>,
 }
 
 impl Challenge {
-    pub fn execute(&mut self, rounds: u32) {
+    pub fn execute(&mut self, rounds: u32, worry: impl Fn(u64) -> u64) -> u64 {
         for _ in 0..rounds {
             for i in 0..self.monkeys.len() {
                 let mut map: Vec<Vec<u64>> = vec![vec![]; self.monkeys.len()];
@@ -123,9 +92,9 @@ impl Challenge {
                 monkey.no_items_inspected += monkey.items.len() as u64;
 
                 monkey.items.iter().for_each(|item| {
-                    let op_result = (monkey.op)(*item);
-                    let op_result = (self.worry)(op_result);
-                    let to_monkey = (monkey.test)(op_result);
+                    let op_result = monkey.op.apply(*item);
+                    let op_result = worry(op_result);
+                    let to_monkey = monkey.test(op_result);
                     map[to_monkey].push(op_result)
                 });
                 monkey.items = Vec::new();
@@ -137,66 +106,78 @@ impl Challenge {
             }
         }
 
-        self.monkeys
-            .sort_by(|a, b| a.no_items_inspected.cmp(&b.no_items_inspected));
+        self.monkeys.sort_by_key(|monkey| monkey.no_items_inspected);
 
-        let monkey_business = self
-            .monkeys
+        self.monkeys
             .iter()
             .rev()
             .take(2)
-            .fold(1, |acc, monkey| acc * monkey.no_items_inspected);
-
-        println!("Monkey business: {}", monkey_business);
+            .fold(1, |acc, monkey| acc * monkey.no_items_inspected)
     }
 }
 
 impl FromStr for Challenge {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let monkeys = s
-            .lines()
-            .collect::<Vec<&str>>()
-            .chunks(7)
-            .map(|monkey_desc| {
-                monkey_desc[1..monkey_desc.len()]
-                    .iter()
-                    .fold(String::new(), |mut acc, line| {
-                        acc.push_str(line);
-                        acc.push('\n');
-                        acc
-                    })
-                    .parse::<Monkey>()
-                    .unwrap()
-            })
-            .collect();
+            .split("\n\n")
+            .map(|monkey_desc| monkey_desc.parse::<Monkey>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (monkey, monkey_desc) in monkeys.iter().zip(s.split("\n\n")) {
+            if monkey.monkey_when_true.max(monkey.monkey_when_false) >= monkeys.len() {
+                return Err(ParseError::new(
+                    "The monkey throws to a monkey that doesn't exist",
+                    monkey_desc,
+                ));
+            }
+        }
 
-        Ok(Self {
-            monkeys,
-            worry: Box::new(|x| x),
-        })
+        Ok(Self { monkeys })
     }
 }
 
-pub fn task_1() {
-    let input = get_input_content(INPUT_PATH);
-    let mut ch = input.parse::<Challenge>().unwrap();
-    ch.worry = Box::new(|x| x / 3);
-    ch.execute(20);
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Challenge;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse::<Challenge>()
+    }
+
+    fn part1(ch: &Self::Parsed) -> Answer {
+        ch.clone().execute(20, |x| x / 3).into()
+    }
+
+    fn part2(ch: &Self::Parsed) -> Answer {
+        // Reference:
+        // https://www.youtube.com/watch?v=0RkTrYDyzmE&t=2659s
+        let magic_number = ch
+            .monkeys
+            .iter()
+            .fold(1, |acc, monkey| acc * monkey.test_no);
+
+        ch.clone().execute(10000, move |x| x % magic_number).into()
+    }
 }
-pub fn task_2() {
-    let input = get_input_content(INPUT_PATH);
-    let mut ch = input.parse::<Challenge>().unwrap();
 
-    // Reference:
-    // https://www.youtube.com/watch?v=0RkTrYDyzmE&t=2659s
-    let magic_number = ch
-        .monkeys
-        .iter()
-        .fold(1, |acc, monkey| acc * monkey.test_no);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    ch.worry = Box::new(move |x| x % magic_number);
+    const EXAMPLE: &str = include_str!("example.txt");
 
-    ch.execute(10000);
+    #[test]
+    fn part1_example() {
+        let parsed = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&parsed), Answer::Int(10605));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part2(&parsed), Answer::Int(2713310158));
+    }
 }
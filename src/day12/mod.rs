@@ -1,169 +1,139 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    str::FromStr,
+use std::str::FromStr;
+
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+    utils::{
+        geom::Vec2,
+        grid::Grid,
+        search::{astar, bfs, Path},
+    },
 };
 
-use crate::utils::get_input_content;
-
-const INPUT_PATH: &str = "inputs/day_12.txt";
-
-#[derive(Default, Clone, Debug, Eq, Hash, PartialEq, Copy)]
-struct Point(usize, usize);
-
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Copy)]
-struct Pos {
-    point: Point,
+pub struct Pos {
+    point: Vec2<usize>,
     value: u8,
 }
 
-struct Map {
-    grid: Vec<Vec<Pos>>,
+pub struct Map {
+    grid: Grid<Pos>,
     start: Pos,
     end: Pos,
 }
 
 impl Map {
-    pub fn find_path(&self, initial: &Pos) -> Option<u32> {
-        // This map will hold the min dist to the Pos
-        let mut shortest: HashMap<&Pos, u32> = HashMap::new();
-
-        // The dist to initial (itself) is 0
-        shortest.insert(initial, 0);
-
-        // Queue with the next elements to visit
-        let mut to_visit: VecDeque<&Pos> = VecDeque::new();
-
-        // Add all neighbors to the queue to be visited
-        to_visit.extend(self.get_neighbors(initial).iter());
-
-        while let Some(curr_pos) = to_visit.pop_front() {
-            // Get all the neighbors of the current pos
-            let all_neighbors = self.get_neighbors(curr_pos);
-
-            // Filter all_neighbors to just those that can reach curr_pos and from the resulting
-            // ones get the min dist
-            let neighbor_with_min_dist = all_neighbors
-                .iter()
-                .filter(|n| n.value + 1 >= curr_pos.value)
-                .filter_map(|valid| shortest.get(valid))
-                .min();
-
-            // If there is no neighbor_with_min_dist, the continue to the next iteration
-            if neighbor_with_min_dist.is_none() {
-                continue;
-            }
-
-            // The new distance to reach curr_pos from that neighbor is calculated adding 1
-            let new_dist = neighbor_with_min_dist.unwrap() + 1;
-
-            // The get the dist_of_curr_pos from the shortest map (or make it equal to u32::MAX if
-            // not in the map yet)
-            let dist_of_curr_pos = shortest.entry(curr_pos).or_insert(u32::MAX);
-
-            if *dist_of_curr_pos > new_dist {
-                *dist_of_curr_pos = new_dist;
-
-                // Add all neighbors to the queue to be visited next
-                to_visit.extend(all_neighbors);
-            }
-        }
+    // The squares reachable from `pos`, which can be at most one higher
+    pub fn get_neighbors(&self, pos: &Pos) -> impl Iterator<Item = Pos> + '_ {
+        let max_height = pos.value + 1;
+
+        self.grid
+            .neighbors4(pos.point.into())
+            .map(|neighbor| self.grid[neighbor])
+            .filter(move |neighbor| neighbor.value <= max_height)
+    }
 
-        // Return the value in shortest associated with the END point
-        shortest.get(&self.end).copied()
+    // The shortest path from `initial` to the end
+    pub fn find_path(&self, initial: &Pos) -> Option<Path<Pos, usize>> {
+        astar(
+            [*initial],
+            |pos| self.get_neighbors(pos).map(|neighbor| (neighbor, 1)),
+            // Every step moves to a neighbour, so this never overestimates
+            |pos| pos.point.manhattan(self.end.point),
+            |pos| *pos == self.end,
+        )
     }
 
-    pub fn get_neighbors(&self, pos: &Pos) -> Vec<&Pos> {
-        let dirs: [(i32, i32); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
-
-        dirs.iter()
-            .map(|dir| (pos.point.0 as i32 + dir.0, pos.point.1 as i32 + dir.1))
-            .filter(|tupl| {
-                tupl.0 >= 0
-                    && tupl.0 < self.grid[0].len() as i32
-                    && tupl.1 >= 0
-                    && tupl.1 < self.grid.len() as i32
-            })
-            .map(|valid_pos| &self.grid[valid_pos.1 as usize][valid_pos.0 as usize])
-            .collect()
+    // The shortest path from any of `sources` to the end
+    pub fn find_closest_path(
+        &self,
+        sources: impl IntoIterator<Item = Pos>,
+    ) -> Option<Path<Pos, usize>> {
+        bfs(
+            sources,
+            |pos| self.get_neighbors(pos),
+            |pos| *pos == self.end,
+        )
     }
 }
 
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut start = None;
-        let mut end = None;
-
-        let grid = s
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, char)| {
-                        let new_pos = Pos {
-                            point: Point(x, y),
-                            value: match char {
-                                'S' => b'a',
-                                'E' => b'z',
-                                _ => char as u8,
-                            },
-                        };
-                        match char {
-                            'S' => start = Some(new_pos),
-                            'E' => end = Some(new_pos),
-                            _ => (),
-                        }
-                        new_pos
-                    })
-                    .collect()
-            })
-            .collect();
+        let chars = Grid::parse(s, |c| match c {
+            'S' | 'E' | 'a'..='z' => Ok(c),
+            _ => Err("Heights must be lowercase letters, S or E"),
+        })?;
+
+        let grid = chars.map(|pos, c| Pos {
+            point: pos.into(),
+            value: match c {
+                'S' => b'a',
+                'E' => b'z',
+                c => *c as u8,
+            },
+        });
+
+        let find = |marker| chars.position(|c| *c == marker).map(|pos| grid[pos]);
 
         Ok(Self {
+            start: find('S').ok_or_else(|| ParseError::new("Missing the start (S)", s))?,
+            end: find('E').ok_or_else(|| ParseError::new("Missing the end (E)", s))?,
             grid,
-            start: start.unwrap(),
-            end: end.unwrap(),
         })
     }
 }
 
-struct Challenge {
-    map: Map,
-}
+pub struct Day12;
 
-impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
+impl Solution for Day12 {
+    type Parsed = Map;
 
-        let map = input.parse::<Map>().unwrap();
-        Self { map }
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse::<Map>()
     }
 
-    pub fn task_1(self) {
-        println!("{}", self.map.find_path(&self.map.start).unwrap());
+    fn part1(map: &Self::Parsed) -> Answer {
+        map.find_path(&map.start).unwrap().cost.into()
     }
 
-    pub fn task_2(&self) {
-        let min = self
-            .map
-            .grid
-            .iter()
-            .flatten()
-            .filter(|pos| pos.value == b'a')
-            .filter_map(|pos| self.map.find_path(pos))
-            .min();
-
-        println!("{:?}", min.unwrap());
+    fn part2(map: &Self::Parsed) -> Answer {
+        let lowest = map.grid.iter().filter(|pos| pos.value == b'a').copied();
+
+        map.find_closest_path(lowest).unwrap().cost.into()
     }
 }
 
-pub fn task_1() {
-    let ch = Challenge::new();
-    ch.task_1();
-}
-pub fn task_2() {
-    let ch = Challenge::new();
-    ch.task_2();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&parsed), Answer::Int(31));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part2(&parsed), Answer::Int(29));
+    }
+
+    #[test]
+    fn path_example() {
+        let map = Day12::parse(EXAMPLE).unwrap();
+        let path = map.find_path(&map.start).unwrap();
+
+        assert_eq!(path.nodes.len(), path.cost + 1);
+        assert_eq!(path.nodes.first(), Some(&map.start));
+        assert_eq!(path.nodes.last(), Some(&map.end));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|step| step[0].point.manhattan(step[1].point) == 1));
+    }
 }
@@ -1,11 +1,17 @@
-use std::{cmp::Ordering, collections::VecDeque, fmt::Display, str::FromStr};
-
-use crate::utils::get_input_content;
-
-const INPUT_PATH: &str = "inputs/day_13.txt";
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    iter::Peekable,
+    str::{CharIndices, FromStr},
+};
+
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+};
 
 #[derive(Debug, PartialEq, Eq)]
-enum PacketItem {
+pub enum PacketItem {
     Single(u32),
     Multi(Vec<PacketItem>),
 }
@@ -65,7 +71,7 @@ impl PartialOrd for PacketItem {
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct Packet {
+pub struct Packet {
     data: PacketItem,
 }
 
@@ -76,47 +82,66 @@ impl Display for Packet {
 }
 
 impl Packet {
-    pub fn process_array(v: &mut VecDeque<char>) -> PacketItem {
+    pub fn data(&self) -> &PacketItem {
+        &self.data
+    }
+
+    // Parses the items of a list whose opening `[` was already consumed, up to its `]`
+    pub fn process_array(
+        line: &str,
+        v: &mut Peekable<CharIndices>,
+    ) -> Result<PacketItem, ParseError> {
         let mut result = Vec::new();
-        let mut temp = String::from("");
+        let mut number_start = None;
 
-        while let Some(c) = v.pop_front() {
+        while let Some((idx, c)) = v.next() {
             match c {
-                '[' => result.push(Self::process_array(v)),
-                ']' => {
-                    if !temp.is_empty() {
-                        result.push(PacketItem::Single(temp.parse().unwrap()));
-                        temp.clear();
+                '[' => result.push(Self::process_array(line, v)?),
+                ']' | ',' => {
+                    if let Some(start) = number_start.take() {
+                        result.push(PacketItem::Single(parse_number(&line[start..idx])?));
                     }
-                    break;
-                }
-                ',' => {
-                    if !temp.is_empty() {
-                        result.push(PacketItem::Single(temp.parse().unwrap()));
-                        temp.clear();
+                    if c == ']' {
+                        return Ok(PacketItem::Multi(result));
                     }
-                    continue;
                 }
-                _ => temp.push(c),
+                '0'..='9' => {
+                    number_start.get_or_insert(idx);
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        "Unexpected character in packet",
+                        &line[idx..idx + c.len_utf8()],
+                    ))
+                }
             }
         }
 
-        PacketItem::Multi(result)
+        Err(ParseError::new("Unclosed list", &line[line.len()..]))
     }
 }
 
 impl FromStr for Packet {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            data: Self::process_array(&mut s.chars().collect::<VecDeque<char>>()),
-        })
+        let mut chars = s.char_indices().peekable();
+
+        if chars.next().map(|(_, c)| c) != Some('[') {
+            return Err(ParseError::new("A packet must start with `[`", s));
+        }
+
+        let data = PacketItem::Multi(vec![Self::process_array(s, &mut chars)?]);
+
+        match chars.peek() {
+            Some(&(idx, _)) => Err(ParseError::new("Unexpected text after packet", &s[idx..])),
+            None => Ok(Self { data }),
+        }
     }
 }
 
 #[derive(Debug)]
-struct Pair {
+pub struct Pair {
     left: Packet,
     right: Packet,
 }
@@ -127,62 +152,91 @@ impl Pair {
     }
 }
 
-struct Challenge {
+pub struct Challenge {
     pairs: Vec<Pair>,
 }
 
 impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
-
+    pub fn new(input: &str) -> Result<Self, ParseError> {
         let pairs = input
             .lines()
             .filter(|l| !l.is_empty())
             .collect::<Vec<&str>>()
             .chunks(2)
-            .map(|ch| Pair {
-                left: ch[0].parse::<Packet>().unwrap(),
-                right: ch[1].parse::<Packet>().unwrap(),
+            .map(|ch| match ch {
+                [left, right] => Ok(Pair {
+                    left: left.parse::<Packet>()?,
+                    right: right.parse::<Packet>()?,
+                }),
+                [left] => Err(ParseError::new(
+                    "Missing the second packet of the pair",
+                    left,
+                )),
+                _ => unreachable!(),
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Self { pairs }
+        Ok(Self { pairs })
     }
 }
 
-pub fn task_1() {
-    let c = Challenge::new();
-    let result = c.pairs.iter().enumerate().fold(0, |mut acc, (idx, p)| {
-        if p.is_in_order() {
-            acc += idx + 1;
-        }
-        acc
-    });
+pub struct Day13;
 
-    println!("Sum of idx: {}", result);
-}
-pub fn task_2() {
-    let c = Challenge::new();
+impl Solution for Day13 {
+    type Parsed = Challenge;
 
-    let mut all_packets = c
-        .pairs
-        .iter()
-        .flat_map(|p| vec![&p.left.data, &p.right.data])
-        .collect::<Vec<&PacketItem>>();
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
+    }
+
+    fn part1(c: &Self::Parsed) -> Answer {
+        let result = c.pairs.iter().enumerate().fold(0, |mut acc, (idx, p)| {
+            if p.is_in_order() {
+                acc += idx + 1;
+            }
+            acc
+        });
+
+        result.into()
+    }
+
+    fn part2(c: &Self::Parsed) -> Answer {
+        let mut all_packets = c
+            .pairs
+            .iter()
+            .flat_map(|p| vec![&p.left.data, &p.right.data])
+            .collect::<Vec<&PacketItem>>();
+
+        let divider_1 = PacketItem::Multi(vec![PacketItem::Multi(vec![PacketItem::Single(2)])]);
+        let divider_2 = PacketItem::Multi(vec![PacketItem::Multi(vec![PacketItem::Single(6)])]);
 
-    let divider_1 = PacketItem::Multi(vec![PacketItem::Multi(vec![PacketItem::Single(2)])]);
-    let divider_2 = PacketItem::Multi(vec![PacketItem::Multi(vec![PacketItem::Single(6)])]);
+        all_packets.push(&divider_1);
+        all_packets.push(&divider_2);
 
-    all_packets.push(&divider_1);
-    all_packets.push(&divider_2);
+        all_packets.sort();
 
-    all_packets.sort();
+        let divider_1_pos = all_packets.iter().position(|p| *p == &divider_1).unwrap();
+        let divider_2_pos = all_packets.iter().position(|p| *p == &divider_2).unwrap();
 
-    let divider_1_pos = all_packets.iter().position(|p| *p == &divider_1).unwrap();
-    let divider_2_pos = all_packets.iter().position(|p| *p == &divider_2).unwrap();
+        ((divider_1_pos + 1) * (divider_2_pos + 1)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
 
-    println!(
-        "Mul of dividers: {}",
-        (divider_1_pos + 1) * (divider_2_pos + 1)
-    )
+    #[test]
+    fn part1_example() {
+        let parsed = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part1(&parsed), Answer::Int(13));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part2(&parsed), Answer::Int(140));
+    }
 }
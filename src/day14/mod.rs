@@ -1,91 +1,161 @@
-use std::{collections::HashSet, str::FromStr};
+use std::{fmt::Display, str::FromStr};
 
-use crate::utils::get_input_content;
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+    utils::{
+        geom::Vec2,
+        grid::{Grid, Pos},
+    },
+};
 
-const INPUT_PATH: &str = "inputs/day_14.txt";
+pub type Cell = Vec2;
 
-#[derive(Debug, Hash, PartialEq, Eq, Copy, Clone)]
-struct Cell(i32, i32);
+// Where the sand comes from
+const SOURCE: Cell = Vec2::new(500, 0);
 
-impl Default for Cell {
-    fn default() -> Self {
-        Self(500, Default::default())
-    }
+fn parse_cell(s: &str) -> Result<Cell, ParseError> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| ParseError::new("Expected a point like `x,y`", s))?;
+
+    Ok(Vec2::new(parse_number(x)?, parse_number(y)?))
 }
 
-impl FromStr for Cell {
-    type Err = ();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(',');
-        Ok(Self(
-            split.next().unwrap().parse().unwrap(),
-            split.next().unwrap().parse().unwrap(),
-        ))
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        };
+        write!(f, "{c}")
     }
 }
 
-#[derive(Debug)]
-struct Cave {
-    cells: HashSet<Cell>,
+// The part of the cave the sand can reach. Sand moves at most one column per row it falls, so
+// it never gets further than the floor's depth from the source.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    tiles: Grid<Tile>,
+    // Column of the cave at x = 0 of the grid
+    left: i32,
+    // Depth of the lowest rock
+    max_y: i32,
 }
 
 impl Cave {
-    pub fn insert_rock_line(cells: &mut HashSet<Cell>, start: &Cell, end: &Cell) {
-        let dx = (end.0 - start.0).signum();
-        let dy = (end.1 - start.1).signum();
+    pub fn insert_rock_line(&mut self, start: &Cell, end: &Cell) {
+        let step = (*end - *start).signum();
+        let mut new_cell = *start;
+
+        loop {
+            self.set(new_cell, Tile::Rock);
+
+            if new_cell == *end {
+                break;
+            }
 
-        let mut new_cell = Cell(start.0 + dx, start.1 + dy);
+            new_cell += step;
+        }
+    }
 
-        while new_cell != *end {
-            cells.insert(new_cell);
+    fn grid_pos(&self, cell: &Cell) -> Option<Pos> {
+        let pos = (
+            usize::try_from(cell.x - self.left).ok()?,
+            usize::try_from(cell.y).ok()?,
+        );
+        self.tiles.contains(pos).then_some(pos)
+    }
 
-            new_cell.0 += dx;
-            new_cell.1 += dy;
+    // Cells out of reach of the sand are left out
+    fn set(&mut self, cell: Cell, tile: Tile) {
+        if let Some(pos) = self.grid_pos(&cell) {
+            self.tiles[pos] = tile;
         }
     }
+
+    fn is_free(&self, cell: &Cell) -> bool {
+        self.grid_pos(cell)
+            .is_none_or(|pos| self.tiles[pos] == Tile::Air)
+    }
+
+    pub fn sand_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|tile| **tile == Tile::Sand)
+            .count()
+    }
 }
 
 impl FromStr for Cave {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            cells: s
-                .lines()
-                .map(|line| {
-                    line.split(" -> ").collect::<Vec<_>>().windows(2).fold(
-                        HashSet::new(),
-                        |mut acc, cells_pair| {
-                            let start = cells_pair[0].parse::<Cell>().unwrap();
-                            let end = cells_pair[1].parse::<Cell>().unwrap();
-
-                            Cave::insert_rock_line(&mut acc, &start, &end);
-
-                            acc.insert(start);
-                            acc.insert(end);
-
-                            acc
-                        },
-                    )
-                })
-                .fold(HashSet::from([Cell(500, 0)]), |mut acc, set| {
-                    acc.extend(set);
-                    acc
-                }),
-        })
+        let mut lines = vec![];
+
+        for line in s.lines() {
+            let points = line.split(" -> ").collect::<Vec<_>>();
+
+            for cells_pair in points.windows(2) {
+                let start = parse_cell(cells_pair[0])?;
+                let end = parse_cell(cells_pair[1])?;
+
+                // Diagonal lines would never reach their end
+                if start.x != end.x && start.y != end.y {
+                    return Err(ParseError::new(
+                        "Rock lines must be horizontal or vertical",
+                        cells_pair[1],
+                    ));
+                }
+
+                lines.push((start, end));
+            }
+        }
+
+        let max_y = lines
+            .iter()
+            .map(|(start, end)| start.y.max(end.y))
+            .max()
+            .unwrap_or_default()
+            .max(0);
+
+        // The floor is 2 below the lowest rock, sand rests at most one above it
+        let reach = max_y + 2;
+        let mut cave = Self {
+            tiles: Grid::new(2 * reach as usize + 1, reach as usize, Tile::Air),
+            left: SOURCE.x - reach,
+            max_y,
+        };
+
+        for (start, end) in lines {
+            cave.insert_rock_line(&start, &end);
+        }
+
+        Ok(cave)
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tiles)
     }
 }
 
 impl Cave {
     pub fn process(&mut self) {
-        let max_y = self.cells.iter().max_by_key(|c| c.1).unwrap().1;
-
         loop {
-            let mut falling_cell = Cell::default();
+            let mut falling_cell = SOURCE;
 
             loop {
-                if falling_cell.1 > max_y {
+                if falling_cell.y > self.max_y {
                     return;
                 }
 
@@ -95,8 +165,10 @@ impl Cave {
                         continue;
                     }
                     None => {
-                        // println!("Inserting: {:?}", new_cell);
-                        self.cells.insert(falling_cell);
+                        self.set(falling_cell, Tile::Sand);
+                        if falling_cell == SOURCE {
+                            return;
+                        }
                         break;
                     }
                 }
@@ -105,14 +177,14 @@ impl Cave {
     }
 
     pub fn process_with_floor(&mut self) {
-        let max_y = self.cells.iter().max_by_key(|c| c.1).unwrap().1 + 2;
+        let floor = self.max_y + 2;
 
         loop {
-            let mut falling_cell = Cell::default();
+            let mut falling_cell = SOURCE;
 
             loop {
-                if falling_cell.1 == max_y - 1 {
-                    self.cells.insert(falling_cell);
+                if falling_cell.y == floor - 1 {
+                    self.set(falling_cell, Tile::Sand);
                     break;
                 }
 
@@ -122,9 +194,8 @@ impl Cave {
                         continue;
                     }
                     None => {
-                        // println!("Inserting: {:?}", new_cell);
-                        self.cells.insert(falling_cell);
-                        if falling_cell == Cell(500, 0) {
+                        self.set(falling_cell, Tile::Sand);
+                        if falling_cell == SOURCE {
                             return;
                         }
                         break;
@@ -136,9 +207,9 @@ impl Cave {
 
     fn new_cell_pos(&self, cell: &Cell) -> Option<Cell> {
         for dx in [0, -1, 1] {
-            let test_cell = Cell(cell.0 + dx, cell.1 + 1);
+            let test_cell = *cell + Vec2::new(dx, 1);
 
-            if self.cells.get(&test_cell).is_none() {
+            if self.is_free(&test_cell) {
                 return Some(test_cell);
             }
         }
@@ -147,36 +218,45 @@ impl Cave {
     }
 }
 
-struct Challenge {
-    cave: Cave,
-}
+pub struct Day14;
 
-impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
+impl Solution for Day14 {
+    type Parsed = Cave;
 
-        Self {
-            cave: input.parse().unwrap(),
-        }
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
     }
-}
 
-pub fn task_1() {
-    let mut ch = Challenge::new();
+    fn part1(cave: &Self::Parsed) -> Answer {
+        let mut cave = cave.clone();
+        cave.process();
+
+        cave.sand_count().into()
+    }
 
-    let rocks_count = ch.cave.cells.len() - 1;
-    ch.cave.process();
+    fn part2(cave: &Self::Parsed) -> Answer {
+        let mut cave = cave.clone();
+        cave.process_with_floor();
 
-    let sands = ch.cave.cells.len() - rocks_count - 1;
-    println!("Sand count: {}", sands);
+        cave.sand_count().into()
+    }
 }
 
-pub fn task_2() {
-    let mut ch = Challenge::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let rocks_count = ch.cave.cells.len();
-    ch.cave.process_with_floor();
+    const EXAMPLE: &str = include_str!("example.txt");
 
-    let sands = ch.cave.cells.len() - rocks_count + 1;
-    println!("Sand count with floor: {}", sands);
+    #[test]
+    fn part1_example() {
+        let parsed = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part1(&parsed), Answer::Int(24));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part2(&parsed), Answer::Int(93));
+    }
 }
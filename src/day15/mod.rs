@@ -1,98 +1,80 @@
 use std::{
     collections::{HashMap, HashSet},
     ops::RangeInclusive,
-    str::{Chars, FromStr},
 };
 
-use crate::utils::get_input_content;
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+    utils::{geom::Vec2, parser::integers},
+};
 
-const INPUT_PATH: &str = "inputs/day_15.txt";
+pub type Pos = Vec2;
 
-#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
-struct Pos(i32, i32);
+// The columns of `line` within `distance` of `center`
+pub fn line_coverage(center: &Pos, distance: i32, line: i32) -> RangeInclusive<i32> {
+    let steps_to_line = center.y.abs_diff(line) as i32;
 
-impl Pos {
-    pub fn manhattan_distance(&self, other: &Self) -> u32 {
-        other.1.abs_diff(self.1) + other.0.abs_diff(self.0)
+    if steps_to_line > distance {
+        return 0..=0;
     }
 
-    pub fn line_coverage(&self, distance: i32, line: i32) -> RangeInclusive<i32> {
-        let steps_to_line = self.1.abs_diff(line) as i32;
-
-        if steps_to_line > distance {
-            return 0..=0;
-        }
+    let reminder_steps = distance - steps_to_line;
 
-        let reminder_steps = distance - steps_to_line;
+    let start = center.x - reminder_steps;
+    let end = center.x + reminder_steps;
 
-        let start = self.0 - reminder_steps;
-        let end = self.0 + reminder_steps;
-
-        if start <= end {
-            start..=end
-        } else {
-            end..=start
-        }
+    if start <= end {
+        start..=end
+    } else {
+        end..=start
     }
 }
 
-impl FromStr for Pos {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s
-            .split(", ")
-            .map(|s| s.split('=').nth(1).unwrap().parse::<i32>().unwrap());
-
-        Ok(Self(iter.next().unwrap(), iter.next().unwrap()))
-    }
-}
+pub type Sensor = Pos;
+pub type Beacon = Pos;
 
-type Sensor = Pos;
-type Beacon = Pos;
+// The row checked in part 1 and the max coordinate of the distress beacon in part 2. The
+// example in the puzzle uses smaller values (10 and 20).
+const LINE: usize = 2000000;
+const LIMIT: usize = 4000000;
 
-struct Challenge {
+pub struct Challenge {
     pairs: HashMap<Sensor, Beacon>,
+    line: usize,
+    limit: usize,
 }
 
 impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
-
-        Self {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
             pairs: input
                 .lines()
-                .map(|line| {
-                    let mut chars_iter = line.chars();
-                    Self::get_pair(&mut chars_iter)
-                })
-                .collect(),
-        }
+                .map(Self::get_pair)
+                .collect::<Result<_, _>>()?,
+            line: LINE,
+            limit: LIMIT,
+        })
     }
 
-    fn get_pair(iter: &mut Chars) -> (Sensor, Beacon) {
-        let sensor = iter
-            .skip_while(|c| c != &'x')
-            .take_while(|c| c != &':')
-            .collect::<String>()
-            .parse::<Pos>()
-            .unwrap();
-
-        let beacon = iter
-            .skip_while(|c| c != &'x')
-            .take_while(|c| c != &':')
-            .collect::<String>()
-            .parse::<Pos>()
-            .unwrap();
-
-        (sensor, beacon)
+    // Parses lines like `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`
+    fn get_pair(line: &str) -> Result<(Sensor, Beacon), ParseError> {
+        match integers(line)?[..] {
+            [sensor_x, sensor_y, beacon_x, beacon_y] => {
+                Ok((Vec2::new(sensor_x, sensor_y), Vec2::new(beacon_x, beacon_y)))
+            }
+            _ => Err(ParseError::new(
+                "Expected the positions of a sensor and of its beacon",
+                line,
+            )),
+        }
     }
 
     pub fn get_no_beacons_positions_in_line(&self, line: usize) -> usize {
         self.pairs
             .iter()
-            .flat_map(|p| {
-                p.0.line_coverage(p.0.manhattan_distance(p.1) as i32, line as i32)
+            .flat_map(|(sensor, beacon)| {
+                line_coverage(sensor, sensor.manhattan(*beacon), line as i32)
             })
             .collect::<HashSet<_>>()
             .len()
@@ -101,7 +83,7 @@ impl Challenge {
     pub fn beacons_in_line(&self, line: usize) -> usize {
         self.pairs
             .values()
-            .filter(|p| p.1 == line as i32)
+            .filter(|beacon| beacon.y == line as i32)
             .collect::<HashSet<_>>()
             .len()
     }
@@ -111,17 +93,18 @@ impl Challenge {
             let mut ranges = self
                 .pairs
                 .iter()
-                .map(|pair| {
-                    pair.0
-                        .line_coverage(pair.0.manhattan_distance(pair.1) as i32, y as i32)
-                })
+                .map(|(sensor, beacon)| line_coverage(sensor, sensor.manhattan(*beacon), y as i32))
                 .filter(|range| range.start() != range.end())
                 .collect::<Vec<_>>();
 
             ranges.sort_by_key(|range| *range.start());
 
             let mut ranges_iter = ranges.iter();
-            let mut current_end = *ranges_iter.next().unwrap().end();
+            let Some(first) = ranges_iter.next() else {
+                // Nothing covers the row, so the first column of the search square is free
+                return Vec2::new(from as i32, y as i32);
+            };
+            let mut current_end = *first.end();
 
             for range in ranges_iter {
                 if range.end() <= &current_end {
@@ -130,7 +113,7 @@ impl Challenge {
 
                 let diff = range.start() - current_end;
                 if range.start() > &current_end && diff > 1 {
-                    let pos = Pos(current_end + 1, y as i32);
+                    let pos = Vec2::new(current_end + 1, y as i32);
                     return pos;
                 }
 
@@ -138,29 +121,78 @@ impl Challenge {
             }
         }
 
-        Pos(0, 0)
+        Pos::default()
     }
 }
 
-pub fn task_1() {
-    let ch = Challenge::new();
+pub struct Day15;
 
-    let line = 2000000;
+impl Solution for Day15 {
+    type Parsed = Challenge;
 
-    let beacons_in_line = ch.beacons_in_line(line);
-    let line_coverage = ch.get_no_beacons_positions_in_line(line);
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
+    }
 
-    println!(
-        "Number of pos with no beacon: {}",
-        line_coverage - beacons_in_line
-    );
+    fn part1(ch: &Self::Parsed) -> Answer {
+        let line = ch.line;
+
+        let beacons_in_line = ch.beacons_in_line(line);
+        let line_coverage = ch.get_no_beacons_positions_in_line(line);
+
+        (line_coverage - beacons_in_line).into()
+    }
+
+    fn part2(ch: &Self::Parsed) -> Answer {
+        let hidden_beacon = ch.get_hidden_beacon_pos(0, ch.limit);
+        let tuning_freq: u64 = 4000000 * hidden_beacon.x as u64 + hidden_beacon.y as u64;
+
+        tuning_freq.into()
+    }
 }
 
-pub fn task_2() {
-    let ch = Challenge::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
 
-    let hidden_beacon = ch.get_hidden_beacon_pos(0, 4000000);
-    let tuning_freq: u128 = 4000000 * hidden_beacon.0 as u128 + hidden_beacon.1 as u128;
+    fn example() -> Challenge {
+        let mut ch = Day15::parse(EXAMPLE).unwrap();
+        ch.line = 10;
+        ch.limit = 20;
+        ch
+    }
 
-    println!("Tuning frequency: {}", tuning_freq);
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day15::part1(&example()), Answer::Int(26));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day15::part2(&example()), Answer::Int(56000011));
+    }
+
+    #[test]
+    fn rejects_lines_without_two_positions() {
+        let err = Day15::parse("Sensor at x=2, y=18: closest beacon is at x=-2")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Expected the positions of a sensor and of its beacon \
+             (found `Sensor at x=2, y=18: closest beacon is at x=-2`)"
+        );
+    }
+
+    #[test]
+    fn rows_without_coverage() {
+        let ch = Day15::parse("").unwrap();
+        assert_eq!(ch.get_hidden_beacon_pos(0, 20), Vec2::new(0, 0));
+
+        // The sensor only reaches its own column 3 rows away
+        let ch = Day15::parse("Sensor at x=2, y=2: closest beacon is at x=5, y=2").unwrap();
+        assert_eq!(ch.get_hidden_beacon_pos(5, 5), Vec2::new(5, 5));
+    }
 }
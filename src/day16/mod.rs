@@ -1,14 +1,13 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    hash::Hash,
-};
-
-use crate::utils::get_input_content;
+use std::{collections::HashMap, hash::Hash};
 
-const INPUT_PATH: &str = "inputs/day_16.txt";
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+    utils::{parser::Parser, search::distances},
+};
 
 #[derive(Default, Debug, Eq)]
-struct Valve {
+pub struct Valve {
     id: String,
     idx: usize,
     rate: usize,
@@ -29,99 +28,92 @@ impl PartialEq for Valve {
     }
 }
 
-struct Challenge {
+pub struct Challenge {
     valves: HashMap<String, Valve>,
 }
 
 impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
-
-        Self {
-            valves: input
-                .lines()
-                .enumerate()
-                .map(|(idx, line)| Self::parse_line(line, idx))
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let (valves, paths): (Vec<_>, Vec<_>) = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| Self::parse_line(line, idx))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        let mut ch = Self {
+            valves: valves
+                .into_iter()
+                .map(|valve| (valve.id.clone(), valve))
                 .collect(),
+        };
+
+        if !ch.valves.contains_key("AA") {
+            return Err(ParseError::new("Missing the starting valve AA", input));
         }
-    }
 
-    fn parse_line(line: &str, idx: usize) -> (String, Valve) {
-        let mut split = line.split_whitespace();
-        let id = split.nth(1).unwrap();
-        let rate = split.nth(2).unwrap();
+        let unknown = paths
+            .iter()
+            .flatten()
+            .find(|path| !ch.valves.contains_key(**path));
+
+        if let Some(path) = unknown {
+            return Err(ParseError::new("Tunnel to an unknown valve", path));
+        }
 
-        let split = split.skip(4);
+        let valve_names = ch.valves.keys().cloned().collect::<Vec<_>>();
 
-        (
-            id.to_string(),
-            Valve {
+        for valve_name in valve_names {
+            ch.valves.get_mut(&valve_name).unwrap().usefull_paths =
+                ch.cost_of_travelling(valve_name.to_string());
+        }
+
+        Ok(ch)
+    }
+
+    // Parses lines like `Valve BB has flow rate=13; tunnels lead to valves CC, AA`, along with
+    // the names of the valves in the line, to point at them in errors
+    fn parse_line(line: &str, idx: usize) -> Result<(Valve, Vec<&str>), ParseError> {
+        Parser::parse_all(line, |p| {
+            let id = p.field("Valve", Parser::word)?;
+            let rate = p.field("has flow rate=", Parser::unsigned)?;
+            p.literal(";")?;
+            p.one_of(&[
+                ("tunnels lead to valves", ()),
+                ("tunnel leads to valve", ()),
+            ])?;
+            let paths = p.list(",", Parser::word)?;
+
+            let valve = Valve {
                 id: id.to_string(),
                 idx,
-                rate: Self::parse_rate(rate),
-                paths: split
-                    .collect::<String>()
-                    .split(',')
-                    .map(|str| str.to_string())
-                    .collect(),
+                rate,
+                paths: paths.iter().map(|path| path.to_string()).collect(),
                 usefull_paths: HashMap::default(),
-            },
-        )
-    }
+            };
 
-    fn parse_rate(rate_str: &str) -> usize {
-        rate_str
-            .split('=')
-            .nth(1)
-            .unwrap()
-            .chars()
-            .take_while(|c| c != &';')
-            .collect::<String>()
-            .parse()
-            .unwrap()
+            Ok((valve, paths))
+        })
     }
 
+    // The number of steps from `start` to every other valve with a rate
     pub fn cost_of_travelling(&self, start: String) -> HashMap<String, usize> {
-        let current = self.valves.get(&start).unwrap();
-
-        // To store the costs
-        let mut costs = HashMap::new();
-
-        // To iterate
-        let mut queue = VecDeque::new();
-        // Add starting point to the queu
-        queue.push_back((current, 0usize));
-
-        // Visited already
-        let mut visited = HashSet::new();
-        // Mark starting point as visited
-        visited.insert(current);
-
-        while let Some((node, cost)) = queue.pop_front() {
-            let paths = &node.paths;
-
-            for p in paths {
-                let valve = self.valves.get(p).unwrap();
-
-                if !visited.insert(valve) {
-                    // Already visited
-                    continue;
-                }
-
-                if valve.rate > 0 {
-                    // Add to the costs only if vale has rate
-                    costs.insert(p.to_string(), cost + 1);
-                }
-                queue.push_back((valve, cost + 1));
-            }
-        }
+        let mut costs = distances([start.as_str()], |valve| {
+            self.valves[*valve].paths.iter().map(String::as_str)
+        });
+        costs.remove(start.as_str());
 
         costs
+            .into_iter()
+            .filter(|(valve, _)| self.valves[*valve].rate > 0)
+            .map(|(valve, cost)| (valve.to_string(), cost))
+            .collect()
     }
 }
 
 #[derive(Eq, Clone)]
-struct SearchState {
+pub struct SearchState {
     current: String,
     // Each pos in the vec represents the Valve with that idx
     // If that pos is true, that Valve is already on
@@ -149,7 +141,7 @@ impl PartialEq for SearchState {
 }
 
 #[derive(Default)]
-struct Search {
+pub struct Search {
     seen: HashMap<SearchState, usize>,
     time: usize,
     helper: bool,
@@ -244,41 +236,52 @@ impl Search {
     }
 }
 
-pub fn task_1() {
-    let mut ch = Challenge::new();
+pub struct Day16;
 
-    let valve_names = ch.valves.keys().cloned().collect::<Vec<_>>();
+impl Solution for Day16 {
+    type Parsed = Challenge;
 
-    for valve_name in valve_names {
-        ch.valves.get_mut(&valve_name).unwrap().usefull_paths =
-            ch.cost_of_travelling(valve_name.to_string());
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
     }
 
-    let mut search = Search {
-        time: 30,
-        ..Default::default()
-    };
-    let max_flow = search.bfs(None, &ch.valves);
+    fn part1(ch: &Self::Parsed) -> Answer {
+        let mut search = Search {
+            time: 30,
+            ..Default::default()
+        };
+        let max_flow = search.bfs(None, &ch.valves);
+
+        max_flow.into()
+    }
+
+    fn part2(ch: &Self::Parsed) -> Answer {
+        let mut search = Search {
+            time: 26,
+            helper: true,
+            ..Default::default()
+        };
+        let max_flow = search.bfs(None, &ch.valves);
 
-    println!("Most possible pressure released: {max_flow}");
+        max_flow.into()
+    }
 }
 
-pub fn task_2() {
-    let mut ch = Challenge::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let valve_names = ch.valves.keys().cloned().collect::<Vec<_>>();
+    const EXAMPLE: &str = include_str!("example.txt");
 
-    for valve_name in valve_names {
-        ch.valves.get_mut(&valve_name).unwrap().usefull_paths =
-            ch.cost_of_travelling(valve_name.to_string());
+    #[test]
+    fn part1_example() {
+        let parsed = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::part1(&parsed), Answer::Int(1651));
     }
 
-    let mut search = Search {
-        time: 26,
-        helper: true,
-        ..Default::default()
-    };
-    let max_flow = search.bfs(None, &ch.valves);
-
-    println!("Most possible pressure released: {max_flow}");
+    #[test]
+    fn part2_example() {
+        let parsed = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::part2(&parsed), Answer::Int(1707));
+    }
 }
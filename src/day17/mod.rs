@@ -1,16 +1,20 @@
 use std::fmt::Display;
 
-use crate::utils::get_input_content;
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+    utils::grid::Grid,
+};
 
-const INPUT_PATH: &str = "inputs/day_17.txt";
+const CHAMBER_WIDTH: usize = 7;
 
-struct Jets {
+pub struct Jets {
     pattern: Vec<char>,
     index: usize,
 }
 
 impl Jets {
-    pub fn new(p: String) -> Self {
+    pub fn new(p: &str) -> Self {
         Self {
             pattern: p.chars().take_while(|c| c == &'>' || c == &'<').collect(),
             index: 0,
@@ -29,30 +33,31 @@ impl Jets {
     }
 }
 
-type Shape = Vec<Vec<bool>>;
+// Rocks are drawn with their top row first, as in the puzzle, while the chamber keeps its bottom
+// row first so it can grow upwards
+pub type Shape = Grid<bool>;
 
 #[derive(Clone)]
-struct Rock {
-    width: usize,
-    height: usize,
+pub struct Rock {
     shape: Shape,
 }
 
 impl Rock {
     pub fn new(shape: Shape) -> Self {
-        let width = shape.get(0).unwrap_or(&Vec::new()).len();
-        let height = shape.len();
+        Self { shape }
+    }
 
-        Self {
-            shape,
-            width,
-            height,
-        }
+    pub fn width(&self) -> usize {
+        self.shape.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.shape.height()
     }
 
     pub fn all_pos(&self) -> Vec<(usize, usize)> {
         self.shape
-            .iter()
+            .rows()
             .rev()
             .enumerate()
             .flat_map(|(y, line)| {
@@ -66,12 +71,12 @@ impl Rock {
 
     pub fn down_limits(&self) -> Vec<(usize, usize)> {
         let mut poss = vec![];
-        for x in 0..self.width {
-            let y = self.height - 1;
-            if self.shape[y][x] {
-                poss.push((x, self.height - y - 1));
+        for x in 0..self.width() {
+            let y = self.height() - 1;
+            if self.shape[(x, y)] {
+                poss.push((x, self.height() - y - 1));
             } else {
-                poss.push((x, self.height - y));
+                poss.push((x, self.height() - y));
             }
         }
         poss
@@ -79,7 +84,7 @@ impl Rock {
 
     pub fn right_limits(&self) -> Vec<(usize, usize)> {
         self.shape
-            .iter()
+            .rows()
             .rev()
             .enumerate()
             .map(|(y, line)| {
@@ -91,7 +96,7 @@ impl Rock {
 
     pub fn left_limits(&self) -> Vec<(usize, usize)> {
         self.shape
-            .iter()
+            .rows()
             .rev()
             .enumerate()
             .map(|(y, line)| {
@@ -104,7 +109,7 @@ impl Rock {
 
 impl From<RockType> for Rock {
     fn from(value: RockType) -> Self {
-        let shape = match value {
+        let shape = Grid::from_rows(match value {
             RockType::LineH => vec![vec![true; 4]],
             RockType::Plus => {
                 vec![
@@ -120,7 +125,7 @@ impl From<RockType> for Rock {
             ],
             RockType::LineV => vec![vec![true], vec![true], vec![true], vec![true]],
             RockType::Square => vec![vec![true; 2], vec![true; 2]],
-        };
+        });
 
         Self::new(shape)
     }
@@ -143,7 +148,7 @@ impl Display for Rock {
         write!(
             f,
             "{}",
-            self.shape.iter().rev().fold(String::new(), |mut acc, v| {
+            self.shape.rows().rev().fold(String::new(), |mut acc, v| {
                 acc.push_str(
                     &v.iter()
                         .map(|v| if *v { '#' } else { '.' })
@@ -157,7 +162,7 @@ impl Display for Rock {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum RockType {
+pub enum RockType {
     LineH,
     Plus,
     InvertedL,
@@ -166,7 +171,7 @@ enum RockType {
 }
 
 #[derive(Clone, Copy)]
-struct RockGen {
+pub struct RockGen {
     last_one: RockType,
 }
 
@@ -185,19 +190,17 @@ impl Iterator for RockGen {
     }
 }
 
-struct Challenge {
+pub struct Challenge {
     jets: Jets,
     chamber: Shape,
     rock_gen: RockGen,
 }
 
 impl Challenge {
-    pub fn new(rg: Option<RockGen>) -> Self {
-        let input = get_input_content(INPUT_PATH);
-
+    pub fn new(input: &str, rg: Option<RockGen>) -> Self {
         Self {
             jets: Jets::new(input),
-            chamber: Vec::new(),
+            chamber: Grid::new(CHAMBER_WIDTH, 0, false),
             rock_gen: rg.unwrap_or(RockGen {
                 last_one: RockType::Square,
             }),
@@ -205,22 +208,14 @@ impl Challenge {
     }
 
     pub fn add_rock(&mut self, rock: &Rock) {
-        let empty_line = vec![false; 7];
-        (0..3).for_each(|_| self.chamber.push(empty_line.clone()));
-
-        let mut new_rock_shape: Shape = rock.into();
-        new_rock_shape.iter_mut().for_each(|line| {
-            line.insert(0, false);
-            line.insert(0, false);
+        (0..3).for_each(|_| self.chamber.push_row(vec![false; CHAMBER_WIDTH]));
 
-            let missing = 7 - line.len();
-            for _ in 0..missing {
-                line.push(false);
-            }
-        });
-        new_rock_shape.reverse();
-
-        self.chamber.extend_from_slice(&new_rock_shape);
+        // Rocks appear two units away from the left wall
+        for line in rock.shape.rows().rev() {
+            let mut row = vec![false; CHAMBER_WIDTH];
+            row[2..2 + line.len()].copy_from_slice(line);
+            self.chamber.push_row(row);
+        }
     }
 
     pub fn simulate(&mut self, rounds: usize) {
@@ -233,7 +228,7 @@ impl Challenge {
             self.add_rock(&falling_rock);
 
             // Left bottom point
-            let mut falling_rock_pos = (2, self.chamber.len() - falling_rock.height);
+            let mut falling_rock_pos = (2, self.chamber.height() - falling_rock.height());
 
             loop {
                 // Handle next jet
@@ -272,7 +267,7 @@ impl Challenge {
             .collect::<Vec<_>>();
 
         for p in poss {
-            if self.chamber[p.1][p.0] && self.chamber[p.1 - 1][p.0] {
+            if self.chamber[p] && self.chamber[(p.0, p.1 - 1)] {
                 return false;
             }
         }
@@ -284,13 +279,13 @@ impl Challenge {
             .collect::<Vec<_>>();
 
         for p in all_pos.iter() {
-            self.chamber[p.1 - 1][p.0] = self.chamber[p.1][p.0];
-            self.chamber[p.1][p.0] = false;
+            self.chamber[(p.0, p.1 - 1)] = self.chamber[*p];
+            self.chamber[*p] = false;
         }
 
-        if let Some(last) = self.chamber.last() {
+        if let Some(last) = self.chamber.rows().last() {
             if last.iter().all(|v| v == &false) {
-                self.chamber.pop();
+                self.chamber.pop_row();
             }
         }
 
@@ -298,7 +293,7 @@ impl Challenge {
     }
 
     fn move_falling_right(&mut self, falling: &Rock, pos: (usize, usize)) -> bool {
-        if pos.0 + falling.width >= self.chamber[0].len() {
+        if pos.0 + falling.width() >= self.chamber.width() {
             return false;
         }
 
@@ -309,7 +304,7 @@ impl Challenge {
             .collect::<Vec<_>>();
 
         for p in poss {
-            if self.chamber[p.1][p.0] && self.chamber[p.1][p.0 + 1] {
+            if self.chamber[p] && self.chamber[(p.0 + 1, p.1)] {
                 return false;
             }
         }
@@ -321,8 +316,8 @@ impl Challenge {
             .collect::<Vec<_>>();
 
         for p in all_pos.iter().rev() {
-            self.chamber[p.1][p.0 + 1] = self.chamber[p.1][p.0];
-            self.chamber[p.1][p.0] = false;
+            self.chamber[(p.0 + 1, p.1)] = self.chamber[*p];
+            self.chamber[*p] = false;
         }
 
         true
@@ -340,7 +335,7 @@ impl Challenge {
             .collect::<Vec<_>>();
 
         for p in poss {
-            if self.chamber[p.1][p.0] && self.chamber[p.1][p.0 - 1] {
+            if self.chamber[p] && self.chamber[(p.0 - 1, p.1)] {
                 return false;
             }
         }
@@ -352,15 +347,16 @@ impl Challenge {
             .collect::<Vec<_>>();
 
         for p in all_pos.iter() {
-            self.chamber[p.1][p.0 - 1] = self.chamber[p.1][p.0];
-            self.chamber[p.1][p.0] = false;
+            self.chamber[(p.0 - 1, p.1)] = self.chamber[*p];
+            self.chamber[*p] = false;
         }
 
         true
     }
 
+    #[cfg(test)]
     fn print_chamber(&self) -> String {
-        self.chamber.iter().rev().fold(String::new(), |mut acc, v| {
+        self.chamber.rows().rev().fold(String::new(), |mut acc, v| {
             acc.push_str(
                 &v.iter()
                     .map(|v| if *v { '#' } else { '.' })
@@ -372,21 +368,51 @@ impl Challenge {
     }
 }
 
-pub fn task_1() {
-    let mut ch = Challenge::new(None);
-    ch.simulate(2022);
-    println!("Chamber height: {}", ch.chamber.len());
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let jets = input.trim();
+
+        if jets.is_empty() {
+            return Err(ParseError::new("Missing the jet pattern", input));
+        }
+
+        match jets.find(|c| c != '<' && c != '>') {
+            Some(idx) => Err(ParseError::new("Jets must be `<` or `>`", &jets[idx..])),
+            None => Ok(jets.to_string()),
+        }
+    }
+
+    fn part1(jets: &Self::Parsed) -> Answer {
+        let mut ch = Challenge::new(jets, None);
+        ch.simulate(2022);
+        ch.chamber.height().into()
+    }
+
+    fn part2(_: &Self::Parsed) -> Answer {
+        Answer::Unimplemented
+    }
 }
-pub fn task_2() {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const JETS: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day17::parse(JETS).unwrap();
+        assert_eq!(Day17::part1(&parsed), Answer::Int(3068));
+    }
+
     // Jets tests
     #[test]
     fn jets_get_next() {
-        let mut ch = Challenge::new(None);
+        let mut ch = Challenge::new(JETS, None);
         assert_eq!(
             (0..80).fold(String::new(), |mut acc, _| {
                 acc.push(*ch.jets.get_next());
@@ -400,30 +426,30 @@ mod tests {
     // Rock tests
     #[test]
     fn line_rock_creation_test() {
-        let rock_shape = vec![vec![true, true, true, true]];
+        let rock_shape = Grid::from_rows(vec![vec![true, true, true, true]]);
         let rock_shape_clone = rock_shape.clone();
 
         let new_rock = Rock::new(rock_shape);
 
         assert_eq!(new_rock.shape, rock_shape_clone);
-        assert_eq!(new_rock.height, 1);
-        assert_eq!(new_rock.width, 4);
+        assert_eq!(new_rock.height(), 1);
+        assert_eq!(new_rock.width(), 4);
     }
 
     #[test]
     fn plus_rock_creation_test() {
-        let rock_shape = vec![
+        let rock_shape = Grid::from_rows(vec![
             vec![false, true, false],
             vec![true, true, true],
             vec![false, true, false],
-        ];
+        ]);
         let rock_shape_clone = rock_shape.clone();
 
         let new_rock = Rock::new(rock_shape);
 
         assert_eq!(new_rock.shape, rock_shape_clone);
-        assert_eq!(new_rock.height, 3);
-        assert_eq!(new_rock.width, 3);
+        assert_eq!(new_rock.height(), 3);
+        assert_eq!(new_rock.width(), 3);
     }
 
     #[test]
@@ -431,7 +457,7 @@ mod tests {
         let line_v = RockType::LineV;
         let rock = Rock::from(line_v);
 
-        let rock_v_shape = vec![vec![true], vec![true], vec![true], vec![true]];
+        let rock_v_shape = Grid::from_rows(vec![vec![true], vec![true], vec![true], vec![true]]);
 
         assert_eq!(rock.shape, rock_v_shape);
     }
@@ -445,11 +471,11 @@ mod tests {
     }
 
     #[test]
-    fn rock_into_vec_test() {
+    fn rock_into_shape_test() {
         let rock = Rock::from(RockType::LineH);
-        let vec: Vec<_> = rock.into();
+        let shape: Shape = rock.into();
 
-        assert_eq!(vec, vec![vec![true; 4]]);
+        assert_eq!(shape, Grid::from_rows(vec![vec![true; 4]]));
     }
 
     #[test]
@@ -550,7 +576,7 @@ mod tests {
     // Challenge tests
     #[test]
     fn add_line_rock_test() {
-        let mut ch = Challenge::new(None);
+        let mut ch = Challenge::new(JETS, None);
         let rock = Rock::from(RockType::LineH);
 
         ch.add_rock(&rock);
@@ -560,7 +586,7 @@ mod tests {
 
     #[test]
     fn add_plus_rock_test() {
-        let mut ch = Challenge::new(None);
+        let mut ch = Challenge::new(JETS, None);
         let rock = Rock::from(RockType::Plus);
 
         ch.add_rock(&rock);
@@ -1,81 +1,44 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    num::ParseIntError,
-    ops::Sub,
-    str::FromStr,
-};
-
-use crate::utils::get_input_content;
-
-const INPUT_PATH: &str = "inputs/day_18.txt";
+use std::collections::HashSet;
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct Cube(i32, i32, i32);
-
-impl Cube {
-    fn is_valid(&self) -> bool {
-        return self.0 >= 0 && self.1 >= 0 && self.2 >= 0;
-    }
-}
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+    utils::{geom::Vec3, search::distances},
+};
 
-impl FromStr for Cube {
-    type Err = ParseIntError;
+pub type Cube = Vec3;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(',');
-        Ok(Self(
-            iter.next().unwrap().parse()?,
-            iter.next().unwrap().parse()?,
-            iter.next().unwrap().parse()?,
-        ))
-    }
-}
+fn parse_cube(s: &str) -> Result<Cube, ParseError> {
+    let coords = s.split(',').collect::<Vec<_>>();
 
-impl Sub for Cube {
-    type Output = Self;
+    let [x, y, z] = coords[..] else {
+        return Err(ParseError::new("Expected a cube like `x,y,z`", s));
+    };
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
-    }
+    Ok(Vec3::new(
+        parse_number(x)?,
+        parse_number(y)?,
+        parse_number(z)?,
+    ))
 }
 
-impl<'a, 'b> Sub<&'b Cube> for &'a Cube {
-    type Output = Cube;
-
-    fn sub(self, rhs: &'b Cube) -> Cube {
-        Cube(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
-    }
-}
-
-struct Challenge {
+pub struct Challenge {
     cubes: Vec<Cube>,
 }
 
 impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
-        Self {
-            cubes: input.lines().map(|l| l.parse().unwrap()).collect(),
-        }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            cubes: input.lines().map(parse_cube).collect::<Result<_, _>>()?,
+        })
     }
 
     pub fn get_surface(&self) -> usize {
         let mut surface = 0;
 
-        let deltas = [
-            Cube(-1, 0, 0),
-            Cube(1, 0, 0),
-            // Same x, same z
-            Cube(0, -1, 0),
-            Cube(0, 1, 0),
-            // Same x, same y
-            Cube(0, 0, -1),
-            Cube(0, 0, 1),
-        ];
-
         for cube in self.cubes.iter() {
-            deltas.iter().for_each(|c| {
-                if !self.cubes.contains(&(cube - c)) {
+            cube.neighbors6().for_each(|n| {
+                if !self.cubes.contains(&n) {
                     surface += 1;
                 }
             });
@@ -87,12 +50,21 @@ impl Challenge {
     pub fn get_external_surface(&self) -> usize {
         let mut surface = 0;
 
-        let min_x = self.cubes.iter().min_by_key(|c| c.0).unwrap().0 - 1;
-        let min_y = self.cubes.iter().min_by_key(|c| c.1).unwrap().1 - 1;
-        let min_z = self.cubes.iter().min_by_key(|c| c.2).unwrap().2 - 1;
-        let max_x = self.cubes.iter().max_by_key(|c| c.0).unwrap().0 + 1;
-        let max_y = self.cubes.iter().max_by_key(|c| c.1).unwrap().1 + 1;
-        let max_z = self.cubes.iter().max_by_key(|c| c.2).unwrap().2 + 1;
+        // Without cubes there is no droplet, so no surface either
+        let Some(first) = self.cubes.first() else {
+            return 0;
+        };
+
+        let (min, max) = self.cubes.iter().fold((*first, *first), |(min, max), c| {
+            (
+                Vec3::new(min.x.min(c.x), min.y.min(c.y), min.z.min(c.z)),
+                Vec3::new(max.x.max(c.x), max.y.max(c.y), max.z.max(c.z)),
+            )
+        });
+
+        // One more layer of air on every side, so the outside surrounds the droplet
+        let (min_x, min_y, min_z) = (min.x - 1, min.y - 1, min.z - 1);
+        let (max_x, max_y, max_z) = (max.x + 1, max.y + 1, max.z + 1);
 
         let mut air_spots = HashSet::new();
 
@@ -100,65 +72,72 @@ impl Challenge {
         for x in min_x..=max_x {
             for y in min_y..=max_y {
                 for z in min_z..=max_z {
-                    if !self.cubes.contains(&Cube(x, y, z)) {
-                        air_spots.insert(Cube(x, y, z));
+                    if !self.cubes.contains(&Vec3::new(x, y, z)) {
+                        air_spots.insert(Vec3::new(x, y, z));
                     }
                 }
             }
         }
 
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::from([Cube(min_x, min_y, min_z)]);
-
-        let deltas = [
-            Cube(-1, 0, 0),
-            Cube(1, 0, 0),
-            // Same x, same z
-            Cube(0, -1, 0),
-            Cube(0, 1, 0),
-            // Same x, same y
-            Cube(0, 0, -1),
-            Cube(0, 0, 1),
-        ];
-
-        while let Some(air) = queue.pop_front() {
-            if visited.contains(&air) {
-                continue;
-            }
-
-            deltas.iter().for_each(|delta| {
-                let n = &air - delta;
-                if self.cubes.contains(&n) {
-                    surface += 1;
-                }
-                if air_spots.contains(&n) {
-                    queue.push_back(n);
-                }
-            });
+        // The air reachable from outside of the droplet
+        let outside = distances([Vec3::new(min_x, min_y, min_z)], |air| {
+            air.neighbors6().filter(|n| air_spots.contains(n))
+        });
 
-            visited.insert(air);
+        for air in outside.keys() {
+            surface += air.neighbors6().filter(|n| self.cubes.contains(n)).count();
         }
 
         surface
     }
 }
 
-pub fn task_1() {
-    let ch = Challenge::new();
-    println!("Surface: {}", ch.get_surface());
-}
-pub fn task_2() {
-    let ch = Challenge::new();
-    println!("Surface water can reach: {}", ch.get_external_surface());
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed = Challenge;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
+    }
+
+    fn part1(ch: &Self::Parsed) -> Answer {
+        ch.get_surface().into()
+    }
+
+    fn part2(ch: &Self::Parsed) -> Answer {
+        ch.get_external_surface().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("example.txt");
+
     #[test]
     fn surface_test() {
-        let ch = Challenge::new();
+        let ch = Challenge::new(EXAMPLE).unwrap();
         assert_eq!(ch.get_surface(), 64);
     }
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part1(&parsed), Answer::Int(64));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part2(&parsed), Answer::Int(58));
+    }
+
+    #[test]
+    fn empty_droplet() {
+        let parsed = Day18::parse("").unwrap();
+        assert_eq!(Day18::part1(&parsed), Answer::Int(0));
+        assert_eq!(Day18::part2(&parsed), Answer::Int(0));
+    }
 }
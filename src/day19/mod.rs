@@ -1,16 +1,18 @@
-use std::{cmp, num::ParseIntError, str::FromStr};
+use std::{cmp, str::FromStr};
 
-use crate::utils::get_input_content;
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+    utils::parser::{PResult, Parser},
+};
 
-const INPUT_PATH: &str = "inputs/day_19.txt";
-
-type Ore = usize;
-type Clay = usize;
-type Obsidian = usize;
-type Geode = usize;
+pub type Ore = usize;
+pub type Clay = usize;
+pub type Obsidian = usize;
+pub type Geode = usize;
 
 #[derive(Debug, Clone)]
-struct Warehouse {
+pub struct Warehouse {
     ore: Ore,
     clay: Clay,
     obsidian: Obsidian,
@@ -29,86 +31,40 @@ impl Default for Warehouse {
 }
 
 #[derive(Default, Debug)]
-struct Blueprint {
+pub struct Blueprint {
     ore: Ore,
     clay: Ore,
     obsidian: (Ore, Clay),
     geode: (Ore, Obsidian),
 }
 
-impl FromStr for Blueprint {
-    type Err = ParseIntError;
+impl Blueprint {
+    // Parses the costs of a sentence like `Each obsidian robot costs 3 ore and 14 clay.`
+    fn costs<const N: usize>(
+        p: &mut Parser,
+        robot: &str,
+        materials: [&str; N],
+    ) -> PResult<[usize; N]> {
+        p.literal(&format!("Each {robot} robot costs"))?;
+
+        let mut result = [0; N];
+        for (idx, (cost, material)) in result.iter_mut().zip(materials).enumerate() {
+            if idx > 0 {
+                p.literal("and")?;
+            }
+            *cost = p.unsigned()?;
+            p.literal(material)?;
+        }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split('.');
-
-        // Ore
-        let ore = split
-            .next()
-            .unwrap()
-            .chars()
-            .skip_while(|c| !c.is_ascii_digit())
-            .take_while(|c| c != &' ')
-            .collect::<String>()
-            .parse::<usize>()
-            .unwrap();
-
-        // Clay
-        let clay = split
-            .next()
-            .unwrap()
-            .chars()
-            .skip_while(|c| !c.is_ascii_digit())
-            .take_while(|c| c != &' ')
-            .collect::<String>()
-            .parse::<usize>()
-            .unwrap();
-
-        // Obsidian
-        let mut obsidian_line_split = split.next().unwrap().split(" and ");
-
-        let o_ore = obsidian_line_split
-            .next()
-            .unwrap()
-            .chars()
-            .skip_while(|c| !c.is_ascii_digit())
-            .take_while(|c| c != &' ')
-            .collect::<String>()
-            .parse::<usize>()
-            .unwrap();
-
-        let o_clay = obsidian_line_split
-            .next()
-            .unwrap()
-            .chars()
-            .skip_while(|c| !c.is_ascii_digit())
-            .take_while(|c| c != &' ')
-            .collect::<String>()
-            .parse::<usize>()
-            .unwrap();
-
-        // Geode
-        let mut geode_line_split = split.next().unwrap().split(" and ");
-
-        let g_ore = geode_line_split
-            .next()
-            .unwrap()
-            .chars()
-            .skip_while(|c| !c.is_ascii_digit())
-            .take_while(|c| c != &' ')
-            .collect::<String>()
-            .parse::<usize>()
-            .unwrap();
-
-        let g_obs = geode_line_split
-            .next()
-            .unwrap()
-            .chars()
-            .skip_while(|c| !c.is_ascii_digit())
-            .take_while(|c| c != &' ')
-            .collect::<String>()
-            .parse::<usize>()
-            .unwrap();
+        p.literal(".")?;
+        Ok(result)
+    }
+
+    fn parse(p: &mut Parser) -> PResult<Self> {
+        let [ore] = Self::costs(p, "ore", ["ore"])?;
+        let [clay] = Self::costs(p, "clay", ["ore"])?;
+        let [o_ore, o_clay] = Self::costs(p, "obsidian", ["ore", "clay"])?;
+        let [g_ore, g_obs] = Self::costs(p, "geode", ["ore", "obsidian"])?;
 
         Ok(Self {
             ore,
@@ -119,8 +75,16 @@ impl FromStr for Blueprint {
     }
 }
 
+impl FromStr for Blueprint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::parse_all(s, Self::parse)
+    }
+}
+
 #[derive(Debug, Clone)]
-enum Robots {
+pub enum Robots {
     Ore = 0,
     Clay,
     Obsidian,
@@ -139,7 +103,7 @@ impl From<Robots> for usize {
 }
 
 #[derive(Default, Debug)]
-struct Factory {
+pub struct Factory {
     id: usize,
     warehouse: Warehouse,
     blueprint: Blueprint,
@@ -156,7 +120,7 @@ impl Factory {
         }
     }
 
-    fn collect_geodes(&mut self, state: FactoryState) -> usize {
+    fn collect_geodes(&self, state: FactoryState) -> usize {
         if state.time == 0 {
             return state.warehouse.geode;
         }
@@ -339,7 +303,7 @@ fn time_to(cost: i32, current: i32, producer: i32) -> i32 {
 }
 
 #[derive(Debug, Clone)]
-struct FactoryState {
+pub struct FactoryState {
     robots: [usize; 4],
     warehouse: Warehouse,
     time: usize,
@@ -363,67 +327,87 @@ impl FactoryState {
     }
 }
 
-struct Challenge {
+pub struct Challenge {
     factories: Vec<Factory>,
 }
 
 impl Challenge {
-    fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
+    fn new(input: &str) -> Result<Self, ParseError> {
         let factories = input
             .lines()
             .map(|line| {
-                let (id_spec, blueprint_spec) = line.split_once(':').unwrap();
-
-                let fac_id = id_spec
-                    .split_whitespace()
-                    .nth(1)
-                    .unwrap()
-                    .parse::<usize>()
-                    .unwrap();
-                let blueprint = blueprint_spec.parse::<Blueprint>().unwrap();
+                Parser::parse_all(line, |p| {
+                    let id = p.field("Blueprint", Parser::unsigned)?;
+                    p.literal(":")?;
 
-                Factory::new(fac_id, blueprint)
+                    Ok(Factory::new(id, Blueprint::parse(p)?))
+                })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Self { factories }
+        Ok(Self { factories })
     }
 }
 
-pub fn task_1() {
-    let mut ch = Challenge::new();
-
-    let result = ch
-        .factories
-        .iter_mut()
-        .map(|fac| {
-            fac.collect_geodes(FactoryState {
-                robots: fac.robots,
-                warehouse: fac.warehouse.clone(),
-                time: 23,
-            }) * fac.id
-        })
-        .sum::<usize>();
+pub struct Day19;
 
-    println!("Sum of all blueprints: {}", result);
-}
+impl Solution for Day19 {
+    type Parsed = Challenge;
 
-pub fn task_2() {
-    let mut ch = Challenge::new();
-
-    let result = ch
-        .factories
-        .iter_mut()
-        .take(3)
-        .map(|fac| {
-            fac.collect_geodes(FactoryState {
-                robots: fac.robots,
-                warehouse: fac.warehouse.clone(),
-                time: 31,
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
+    }
+
+    fn part1(ch: &Self::Parsed) -> Answer {
+        let result = ch
+            .factories
+            .iter()
+            .map(|fac| {
+                fac.collect_geodes(FactoryState {
+                    robots: fac.robots,
+                    warehouse: fac.warehouse.clone(),
+                    time: 23,
+                }) * fac.id
             })
-        })
-        .product::<usize>();
+            .sum::<usize>();
+
+        result.into()
+    }
+
+    fn part2(ch: &Self::Parsed) -> Answer {
+        let result = ch
+            .factories
+            .iter()
+            .take(3)
+            .map(|fac| {
+                fac.collect_geodes(FactoryState {
+                    robots: fac.robots,
+                    warehouse: fac.warehouse.clone(),
+                    time: 31,
+                })
+            })
+            .product::<usize>();
 
-    println!("Product of first 3 blueprints: {}", result);
+        result.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part1(&parsed), Answer::Int(33));
+    }
+
+    #[test]
+    #[ignore = "slow without optimizations, run with `cargo test --release -- --ignored`"]
+    fn part2_example() {
+        let parsed = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part2(&parsed), Answer::Int(3472));
+    }
 }
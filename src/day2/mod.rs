@@ -1,40 +1,47 @@
-use crate::utils::get_input_content;
-use std::ops::Add;
-
-const INPUT_PATH: &str = "inputs/day_2.txt";
+use crate::{
+    error::{parse_lines, ParseError},
+    solution::{Answer, Solution},
+};
+use std::{ops::Add, str::FromStr};
 
 // OPONENT MOVES
-enum OponentMoves {
+#[derive(Clone, Copy)]
+pub enum OponentMoves {
     A, // Rock
     B, // Paper
     C, // Scissors
 }
 
-impl From<&str> for OponentMoves {
-    fn from(m: &str) -> Self {
+impl FromStr for OponentMoves {
+    type Err = ParseError;
+
+    fn from_str(m: &str) -> Result<Self, Self::Err> {
         match m {
-            "A" => OponentMoves::A,
-            "B" => OponentMoves::B,
-            "C" => OponentMoves::C,
-            _ => panic!("Something wrong happens"),
+            "A" => Ok(OponentMoves::A),
+            "B" => Ok(OponentMoves::B),
+            "C" => Ok(OponentMoves::C),
+            _ => Err(ParseError::new("Expected A, B or C", m)),
         }
     }
 }
 
 // MY MOVES
-enum MyMoves {
+#[derive(Clone, Copy)]
+pub enum MyMoves {
     X, // Rock
     Y, // Paper
     Z, // Scissors
 }
 
-impl From<&str> for MyMoves {
-    fn from(m: &str) -> Self {
+impl FromStr for MyMoves {
+    type Err = ParseError;
+
+    fn from_str(m: &str) -> Result<Self, Self::Err> {
         match m {
-            "X" => MyMoves::X,
-            "Y" => MyMoves::Y,
-            "Z" => MyMoves::Z,
-            _ => panic!("Something wrong happens"),
+            "X" => Ok(MyMoves::X),
+            "Y" => Ok(MyMoves::Y),
+            "Z" => Ok(MyMoves::Z),
+            _ => Err(ParseError::new("Expected X, Y or Z", m)),
         }
     }
 }
@@ -50,22 +57,28 @@ impl MyMoves {
 }
 
 // PLAY
-struct Play {
+pub struct Play {
     their: OponentMoves,
     mine: MyMoves,
 }
 
-impl From<&str> for Play {
-    fn from(play_line: &str) -> Self {
-        let mut split = play_line.split_whitespace();
+impl FromStr for Play {
+    type Err = ParseError;
 
-        let their = split.next().unwrap();
-        let mine = split.next().unwrap();
+    fn from_str(play_line: &str) -> Result<Self, Self::Err> {
+        let mut split = play_line.split_whitespace();
 
-        Play {
-            their: OponentMoves::from(their),
-            mine: MyMoves::from(mine),
-        }
+        let their = split
+            .next()
+            .ok_or_else(|| ParseError::new("Missing the oponent move", play_line))?;
+        let mine = split
+            .next()
+            .ok_or_else(|| ParseError::new("Missing my move", play_line))?;
+
+        Ok(Play {
+            their: their.parse()?,
+            mine: mine.parse()?,
+        })
     }
 }
 
@@ -116,34 +129,29 @@ impl Play {
         }
     }
 
-    fn from_elf_strategy(play_line: &str) -> Self {
-        let mut split = play_line.split_whitespace();
-
-        let their = split.next().unwrap();
-        let their = OponentMoves::from(their);
-
-        let mine = split.next().unwrap();
+    // Reinterprets my column as the result the elf wants
+    fn from_elf_strategy(play: &Play) -> Self {
+        let their = play.their;
 
-        let (their, mine) = match mine {
+        let (their, mine) = match play.mine {
             // I need to lose
-            "X" => match their {
+            MyMoves::X => match their {
                 OponentMoves::A => (their, MyMoves::Z),
                 OponentMoves::B => (their, MyMoves::X),
                 OponentMoves::C => (their, MyMoves::Y),
             },
             // I need to draw
-            "Y" => match their {
+            MyMoves::Y => match their {
                 OponentMoves::A => (their, MyMoves::X),
                 OponentMoves::B => (their, MyMoves::Y),
                 OponentMoves::C => (their, MyMoves::Z),
             },
             // I need to win
-            "Z" => match their {
+            MyMoves::Z => match their {
                 OponentMoves::A => (their, MyMoves::Y),
                 OponentMoves::B => (their, MyMoves::Z),
                 OponentMoves::C => (their, MyMoves::X),
             },
-            _ => panic!("Wrong input data"),
         };
 
         Play { their, mine }
@@ -151,7 +159,7 @@ impl Play {
 }
 
 // POINTS BASED ON PLAY RESULT
-enum PointForResult {
+pub enum PointForResult {
     Lose,
     Draw,
     Win,
@@ -169,24 +177,44 @@ impl Add<u32> for PointForResult {
     }
 }
 
-pub fn task_1() {
-    let input = get_input_content(INPUT_PATH);
+pub struct Day2;
 
-    let points = input.lines().fold(0, |acc, line| {
-        let play = Play::from(line);
-        acc + play.get_points()
-    });
+impl Solution for Day2 {
+    type Parsed = Vec<Play>;
 
-    println!("Points: {}", points);
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(plays: &Self::Parsed) -> Answer {
+        let points = plays.iter().fold(0, |acc, play| acc + play.get_points());
+        points.into()
+    }
+
+    fn part2(plays: &Self::Parsed) -> Answer {
+        let points = plays.iter().fold(0, |acc, play| {
+            let play = Play::from_elf_strategy(play);
+            acc + play.get_points()
+        });
+        points.into()
+    }
 }
 
-pub fn task_2() {
-    let input = get_input_content(INPUT_PATH);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
 
-    let points = input.lines().fold(0, |acc, line| {
-        let play = Play::from_elf_strategy(line);
-        acc + play.get_points()
-    });
+    #[test]
+    fn part1_example() {
+        let parsed = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part1(&parsed), Answer::Int(15));
+    }
 
-    println!("Points using elf strategy: {}", points);
+    #[test]
+    fn part2_example() {
+        let parsed = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part2(&parsed), Answer::Int(12));
+    }
 }
@@ -1,23 +1,29 @@
-use crate::utils::get_input_content;
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+};
 
-const INPUT_PATH: &str = "inputs/day_20.txt";
+pub type Elem = (i64, bool, usize);
 
-type Elem = (i64, bool, usize);
-
-struct Challenge {
+#[derive(Clone)]
+pub struct Challenge {
     data: Vec<Elem>,
 }
 
 impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
-        Self {
-            data: input
-                .lines()
-                .enumerate()
-                .map(|(idx, i)| (i.parse().unwrap(), false, idx))
-                .collect(),
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let data = input
+            .lines()
+            .enumerate()
+            .map(|(idx, i)| Ok((parse_number(i)?, false, idx)))
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        // The coordinates are read relative to the 0
+        if !data.iter().any(|item| item.0 == 0) {
+            return Err(ParseError::new("The file must contain a 0", input));
         }
+
+        Ok(Self { data })
     }
 
     pub fn process(&mut self, rounds: usize) -> i64 {
@@ -56,18 +62,42 @@ impl Challenge {
     }
 }
 
-pub fn task_1() {
-    let mut ch = Challenge::new();
-    let result = ch.process(1);
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = Challenge;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
+    }
+
+    fn part1(ch: &Self::Parsed) -> Answer {
+        ch.clone().process(1).into()
+    }
+
+    fn part2(ch: &Self::Parsed) -> Answer {
+        let mut ch = ch.clone();
 
-    println!("Sum of grove coords: {result}");
+        ch.data.iter_mut().for_each(|i| i.0 *= 811589153);
+        ch.process(10).into()
+    }
 }
 
-pub fn task_2() {
-    let mut ch = Challenge::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    ch.data.iter_mut().for_each(|i| i.0 *= 811589153);
-    let result = ch.process(10);
+    const EXAMPLE: &str = include_str!("example.txt");
 
-    println!("Sum of grove coords (decryption): {result}");
+    #[test]
+    fn part1_example() {
+        let parsed = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::part1(&parsed), Answer::Int(3));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::part2(&parsed), Answer::Int(1623178306));
+    }
 }
@@ -5,12 +5,13 @@ use std::{
     str::FromStr,
 };
 
-use crate::utils::get_input_content;
-
-const INPUT_PATH: &str = "inputs/day_21.txt";
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+};
 
 #[derive(Debug, Clone)]
-enum Ops {
+pub enum Ops {
     Add,
     Sub,
     Mul,
@@ -18,13 +19,13 @@ enum Ops {
 }
 
 #[derive(Debug, Clone)]
-enum NodeValue {
+pub enum NodeValue {
     Value(isize),
     Op(Ops),
 }
 
 #[derive(Debug, Clone)]
-struct Node {
+pub struct Node {
     id: String,
     value: NodeValue,
     result: isize,
@@ -34,6 +35,15 @@ struct Node {
 }
 
 impl Node {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    // The value of the node, once the tree was reduced
+    pub fn result(&self) -> isize {
+        self.result
+    }
+
     pub fn add_node(parent: Rc<RefCell<Node>>, child: Rc<RefCell<Node>>, left: bool) {
         child.borrow_mut().set_parent(Rc::clone(&parent));
         if left {
@@ -117,11 +127,15 @@ impl Default for Node {
 }
 
 #[derive(Debug)]
-struct BinaryTree {
+pub struct BinaryTree {
     root: Rc<RefCell<Node>>,
 }
 
 impl BinaryTree {
+    pub fn root(&self) -> &Rc<RefCell<Node>> {
+        &self.root
+    }
+
     pub fn reduce(&self) -> isize {
         Self::process(&self.root)
     }
@@ -217,32 +231,51 @@ impl BinaryTree {
 }
 
 impl FromStr for BinaryTree {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let nodes = s
             .lines()
-            .map(|l| l.split_once(": ").unwrap())
-            .collect::<HashMap<_, _>>();
+            .map(|l| {
+                l.split_once(": ")
+                    .ok_or_else(|| ParseError::new("Expected `<monkey>: <job>`", l))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        // `depth` guards against monkeys waiting on each other in a loop
+        fn parse_node(
+            node: (&str, &str),
+            nodes: &HashMap<&str, &str>,
+            depth: usize,
+        ) -> Result<Rc<RefCell<Node>>, ParseError> {
+            if depth > nodes.len() {
+                return Err(ParseError::new("Monkeys are waiting on each other", node.0));
+            }
+
+            let child = |id: &str| {
+                let job = nodes
+                    .get(id)
+                    .ok_or_else(|| ParseError::new("Unknown monkey", id))?;
+                parse_node((id, job), nodes, depth + 1)
+            };
 
-        fn parse_node(node: &(&str, &str), nodes: &HashMap<&str, &str>) -> Rc<RefCell<Node>> {
             match node.1.contains(' ') {
                 true => {
-                    let mut parts = node.1.split_whitespace();
-
-                    let left_id = parts.next().unwrap();
-                    let left = parse_node(&(left_id, *nodes.get(left_id).unwrap()), nodes);
+                    let parts = node.1.split(' ').collect::<Vec<_>>();
+                    let [left_id, op, right_id] = parts[..] else {
+                        return Err(ParseError::new("Expected `<monkey> <op> <monkey>`", node.1));
+                    };
 
-                    let op = match parts.next().unwrap() {
+                    let op = match op {
                         "+" => Ops::Add,
                         "-" => Ops::Sub,
                         "*" => Ops::Mul,
                         "/" => Ops::Div,
-                        _ => unreachable!("This shouldn't happen"),
+                        _ => return Err(ParseError::new("Unknown operation", op)),
                     };
 
-                    let right_id = parts.next().unwrap();
-                    let right = parse_node(&(right_id, *nodes.get(right_id).unwrap()), nodes);
+                    let left = child(left_id)?;
+                    let right = child(right_id)?;
 
                     let node = Node {
                         id: node.0.to_string(),
@@ -254,69 +287,93 @@ impl FromStr for BinaryTree {
                     Node::add_node(Rc::clone(&node_ref), left, true);
                     Node::add_node(Rc::clone(&node_ref), right, false);
 
-                    node_ref
+                    Ok(node_ref)
                 }
-                false => Rc::new(RefCell::new(Node {
+                false => Ok(Rc::new(RefCell::new(Node {
                     id: node.0.to_string(),
-                    value: NodeValue::Value(node.1.parse::<isize>().unwrap()),
+                    value: NodeValue::Value(parse_number(node.1)?),
                     ..Default::default()
-                })),
+                }))),
             }
         }
 
+        let root = nodes
+            .get("root")
+            .ok_or_else(|| ParseError::new("Missing the root monkey", s))?;
+
         Ok(Self {
-            root: parse_node(&("root", nodes.get("root").unwrap()), &nodes),
+            root: parse_node(("root", root), &nodes, 0)?,
         })
     }
 }
 
-struct Challenge {
+pub struct Challenge {
     tree: BinaryTree,
 }
 
 impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
-        Self {
-            tree: input.parse::<BinaryTree>().unwrap(),
-        }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            tree: input.parse::<BinaryTree>()?,
+        })
     }
 }
 
-pub fn task_1() {
-    let ch = Challenge::new();
-    println!("Root monkey yells {}", ch.tree.reduce());
-}
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed = Challenge;
 
-pub fn task_2() {
-    let ch = Challenge::new();
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
+    }
+
+    fn part1(ch: &Self::Parsed) -> Answer {
+        ch.tree.reduce().into()
+    }
 
-    ch.tree.reduce();
+    fn part2(ch: &Self::Parsed) -> Answer {
+        ch.tree.reduce();
 
-    // TODO:
-    // This works for my test input but I need to update the code to find the right branch.
-    // The branch that doesn't contains "humn"
+        // TODO:
+        // This works for my test input but I need to update the code to find the right branch.
+        // The branch that doesn't contains "humn"
 
-    let v = ch
-        .tree
-        .root
-        .borrow()
-        .right
-        .as_ref()
-        .unwrap()
-        .borrow()
-        .result;
+        let v = ch
+            .tree
+            .root
+            .borrow()
+            .right
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .result;
 
-    println!("I (humn) should yell: {}", ch.tree.reduce_to_root(v));
+        ch.tree.reduce_to_root(v).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("example.txt");
+
     #[test]
     fn task_1_test() {
-        let ch = Challenge::new();
-        assert_eq!(87457751482938, ch.tree.reduce())
+        let ch = Challenge::new(EXAMPLE).unwrap();
+        assert_eq!(152, ch.tree.reduce())
+    }
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(Day21::part1(&parsed), Answer::Int(152));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(Day21::part2(&parsed), Answer::Int(301));
     }
 }
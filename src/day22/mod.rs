@@ -1,37 +1,35 @@
 use std::{fmt::Display, str::FromStr};
 
-use crate::utils::get_input_content;
-
-const INPUT_PATH: &str = "inputs/day_22.txt";
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+    utils::grid::Grid,
+};
 
 #[derive(Debug, Copy, Clone)]
-enum Dir {
+pub enum Dir {
     Left,
     Right,
 }
 
-impl From<char> for Dir {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Dir {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'L' => Dir::Left,
-            'R' => Dir::Right,
-            _ => unreachable!("Not a valid direction"),
+            'L' => Ok(Dir::Left),
+            'R' => Ok(Dir::Right),
+            _ => Err(value),
         }
     }
 }
 
 #[derive(Debug)]
-enum Cmd {
+pub enum Cmd {
     Move(i8),
     Rotate(Dir),
 }
 
-impl From<char> for Cmd {
-    fn from(value: char) -> Self {
-        Cmd::Rotate(value.into())
-    }
-}
-
 impl From<i8> for Cmd {
     fn from(value: i8) -> Self {
         Cmd::Move(value)
@@ -48,7 +46,7 @@ impl From<Cmd> for i8 {
 }
 
 #[derive(Debug, Clone)]
-enum Facing {
+pub enum Facing {
     Left,
     Right,
     Top,
@@ -81,20 +79,22 @@ impl From<Facing> for usize {
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum Cell {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cell {
     Open,
     Wall,
     Void,
 }
 
-impl From<char> for Cell {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Cell {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '.' => Cell::Open,
-            '#' => Cell::Wall,
-            ' ' => Cell::Void,
-            _ => unreachable!("Not valid cell char"),
+            '.' => Ok(Cell::Open),
+            '#' => Ok(Cell::Wall),
+            ' ' => Ok(Cell::Void),
+            _ => Err(value),
         }
     }
 }
@@ -109,54 +109,77 @@ impl From<&Cell> for char {
     }
 }
 
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(self))
+    }
+}
+
+// Rows shorter than the widest one are padded with `Void`
 #[derive(Debug)]
-struct Map {
-    map: Vec<Vec<Cell>>,
+pub struct Map {
+    map: Grid<Cell>,
     commands: Vec<Cmd>,
 }
 
 impl Display for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.map
-                .iter()
-                .map(|row| row.iter().map(char::from).collect::<String>())
-                .collect::<Vec<_>>()
-                .join("\n")
-        )
+        let map = self.map.to_string();
+        let rows = map.lines().map(str::trim_end).collect::<Vec<_>>();
+
+        write!(f, "{}", rows.join("\n"))
     }
 }
 
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let Some((map, commands)) = s.split_once("\n\n") else { todo!() };
+        let Some((map, commands)) = s.split_once("\n\n") else {
+            return Err(ParseError::new(
+                "The map and the path must be separated by an empty line",
+                s,
+            ));
+        };
+
+        let map = Grid::parse_padded(map, Cell::Void, |c| {
+            Cell::try_from(c).map_err(|_| "Unknown tile")
+        })?;
+
+        if !map.row(0).iter().any(|cell| cell == &Cell::Open) {
+            return Err(ParseError::new(
+                "The first row of the map must have an open tile",
+                s,
+            ));
+        }
 
-        let mut temp = String::new();
+        let commands = commands.trim_end();
         let mut cmds: Vec<Cmd> = Vec::new();
+        let mut number_start = 0;
+
+        for (idx, c) in commands.char_indices() {
+            if c.is_ascii_digit() {
+                continue;
+            }
+
+            if number_start < idx {
+                cmds.push(parse_number::<i8>(&commands[number_start..idx])?.into());
+            }
+
+            let dir = Dir::try_from(c).map_err(|_| {
+                ParseError::new(
+                    "Expected a number, L or R",
+                    &commands[idx..idx + c.len_utf8()],
+                )
+            })?;
+            cmds.push(Cmd::Rotate(dir));
+
+            number_start = idx + c.len_utf8();
+        }
 
-        let map = map
-            .lines()
-            .map(|line| line.chars().map(|c| c.into()).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-
-        commands
-            .chars()
-            .take_while(|c| !c.is_whitespace())
-            .for_each(|c| match c.is_ascii_digit() {
-                true => {
-                    temp.push(c);
-                }
-                false => {
-                    cmds.push(temp.parse::<i8>().unwrap().into());
-                    temp.clear();
-
-                    cmds.push(c.into());
-                }
-            });
+        if number_start < commands.len() {
+            cmds.push(parse_number::<i8>(&commands[number_start..])?.into());
+        }
 
         Ok(Self {
             map,
@@ -166,7 +189,7 @@ impl FromStr for Map {
 }
 
 #[derive(Debug)]
-struct Actor {
+pub struct Actor {
     facing: Facing,
     pos: (usize, usize),
 }
@@ -178,20 +201,16 @@ impl Actor {
 }
 
 #[derive(Debug)]
-struct Challenge {
-    map: Map,
+pub struct Challenge<'a> {
+    map: &'a Map,
     actor: Actor,
 }
 
-impl Challenge {
-    pub fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
-        let map: Map = input.parse().unwrap();
-
+impl<'a> Challenge<'a> {
+    pub fn new(map: &'a Map) -> Self {
         let initial_pos = (
             map.map
-                .get(0)
-                .unwrap()
+                .row(0)
                 .iter()
                 .position(|col| col == &Cell::Open)
                 .unwrap(),
@@ -213,7 +232,7 @@ impl Challenge {
             match cmd {
                 Cmd::Move(n) => match self.actor.facing {
                     Facing::Left => {
-                        let row = self.map.map.get(actor_pos.1).unwrap();
+                        let row = self.map.map.row(actor_pos.1);
                         let last = row
                             .iter()
                             .enumerate()
@@ -230,7 +249,7 @@ impl Challenge {
                         }
                     }
                     Facing::Right => {
-                        let row = self.map.map.get(actor_pos.1).unwrap();
+                        let row = self.map.map.row(actor_pos.1);
                         let last = row
                             .iter()
                             .enumerate()
@@ -249,12 +268,11 @@ impl Challenge {
                         let last = self
                             .map
                             .map
-                            .iter()
+                            .column(actor_pos.0)
                             .enumerate()
                             .rev()
                             .cycle()
-                            .map(|(idx, row)| (idx, row.get(actor_pos.0).unwrap_or(&Cell::Void)))
-                            .skip(self.map.map.len() - actor_pos.1)
+                            .skip(self.map.map.height() - actor_pos.1)
                             .filter(|(_, cell)| *cell != &Cell::Void)
                             .take(*n as usize)
                             .take_while(|(_, cell)| *cell == &Cell::Open)
@@ -268,10 +286,9 @@ impl Challenge {
                         let last = self
                             .map
                             .map
-                            .iter()
+                            .column(actor_pos.0)
                             .enumerate()
                             .cycle()
-                            .map(|(idx, row)| (idx, row.get(actor_pos.0).unwrap_or(&Cell::Void)))
                             .skip(actor_pos.1 + 1)
                             .filter(|(_, cell)| *cell != &Cell::Void)
                             .take(*n as usize)
@@ -295,11 +312,36 @@ impl Challenge {
     }
 }
 
-pub fn task_1() {
-    let mut ch = Challenge::new();
-    ch.process_commands();
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(map: &Self::Parsed) -> Answer {
+        let mut ch = Challenge::new(map);
+        ch.process_commands();
 
-    println!("The password is: {}", ch.get_password());
+        ch.get_password().into()
+    }
+
+    fn part2(_: &Self::Parsed) -> Answer {
+        Answer::Unimplemented
+    }
 }
 
-pub fn task_2() {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(Day22::part1(&parsed), Answer::Int(6032));
+    }
+}
@@ -1,11 +1,13 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, str::FromStr};
 
-use crate::utils::get_input_content;
-
-const INPUT_PATH: &str = "inputs/day_3.txt";
+use crate::{
+    error::{parse_lines, ParseError},
+    solution::{Answer, Solution},
+};
 
 // Represents each rucksack
-struct Rucksack {
+#[derive(Clone)]
+pub struct Rucksack {
     first_half: String,
     second_half: String,
 }
@@ -19,23 +21,61 @@ impl Rucksack {
         }
     }
 
-    fn item_in_both_halves(&self) -> char {
+    fn item_in_both_halves(&self) -> Option<char> {
         let first_set: HashSet<char> = self.first_half.chars().collect();
         let second_set: HashSet<char> = self.second_half.chars().collect();
 
-        *first_set.intersection(&second_set).next().unwrap()
+        first_set.intersection(&second_set).next().copied()
+    }
+}
+
+impl FromStr for Rucksack {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(ParseError::new(
+                "Items must be letters",
+                &line[idx..idx + c.len_utf8()],
+            ));
+        }
+
+        if !line.len().is_multiple_of(2) {
+            return Err(ParseError::new(
+                "Rucksacks must have the same number of items in both halves",
+                line,
+            ));
+        }
+
+        let rucksack = Self::new(line);
+        if rucksack.item_in_both_halves().is_none() {
+            return Err(ParseError::new(
+                "Both halves of a rucksack must share an item",
+                line,
+            ));
+        }
+
+        Ok(rucksack)
     }
 }
 
 // Group of 3 elves
-struct Group {
+pub struct Group {
     a: Rucksack,
     b: Rucksack,
     c: Rucksack,
 }
 
 impl Group {
-    fn badge_item(&self) -> char {
+    fn new(chunk: &[Rucksack]) -> Self {
+        Self {
+            a: chunk[0].clone(),
+            b: chunk[1].clone(),
+            c: chunk[2].clone(),
+        }
+    }
+
+    fn badge_item(&self) -> Option<char> {
         let a_set: HashSet<char> = format!("{}{}", self.a.first_half, self.a.second_half)
             .chars()
             .collect();
@@ -48,13 +88,10 @@ impl Group {
 
         let a_and_b: Vec<&char> = a_set.intersection(&b_set).collect();
 
-        for item in a_and_b {
-            if b_set.intersection(&c_set).any(|x| x == item) {
-                return *item;
-            }
-        }
-
-        panic!("Input error");
+        a_and_b
+            .into_iter()
+            .find(|item| c_set.contains(item))
+            .copied()
     }
 }
 
@@ -69,35 +106,86 @@ fn item_priority(c: char) -> i32 {
     }
 }
 
-pub fn task_1() {
-    let input = get_input_content(INPUT_PATH);
+pub struct Day3;
 
-    let sum = input.lines().map(Rucksack::new).fold(0, |acc, item| {
-        let in_both = item.item_in_both_halves();
-        let priority = item_priority(in_both);
+impl Solution for Day3 {
+    type Parsed = Vec<Rucksack>;
 
-        acc + priority
-    });
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let rucksacks: Vec<Rucksack> = parse_lines(input)?;
 
-    println!("Sum of priorities: {}", sum);
-}
+        if !rucksacks.len().is_multiple_of(3) {
+            let last = input.lines().last().unwrap_or_default();
+            return Err(ParseError::new("Elves must be in groups of 3", last));
+        }
 
-pub fn task_2() {
-    let input = get_input_content(INPUT_PATH);
-
-    let sum = input
-        .lines()
-        .collect::<Vec<&str>>()
-        .chunks(3)
-        .map(|chunk| Group {
-            a: Rucksack::new(chunk[0]),
-            b: Rucksack::new(chunk[1]),
-            c: Rucksack::new(chunk[2]),
-        })
-        .fold(0, |acc, group| {
-            let badge = group.badge_item();
+        let lines = input.lines().collect::<Vec<_>>();
+        for (chunk, lines) in rucksacks.chunks(3).zip(lines.chunks(3)) {
+            if Group::new(chunk).badge_item().is_none() {
+                return Err(ParseError::new(
+                    "The elves of a group must share a badge item",
+                    lines[0],
+                ));
+            }
+        }
+
+        Ok(rucksacks)
+    }
+
+    fn part1(rucksacks: &Self::Parsed) -> Answer {
+        let sum = rucksacks.iter().fold(0, |acc, item| {
+            let in_both = item
+                .item_in_both_halves()
+                .expect("The parser checked that both halves share an item");
+            let priority = item_priority(in_both);
+
+            acc + priority
+        });
+
+        sum.into()
+    }
+
+    fn part2(rucksacks: &Self::Parsed) -> Answer {
+        let sum = rucksacks.chunks(3).map(Group::new).fold(0, |acc, group| {
+            let badge = group
+                .badge_item()
+                .expect("The parser checked that every group has a badge");
             acc + item_priority(badge)
         });
 
-    println!("Sum of badges priorities: {}", sum);
+        sum.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part1(&parsed), Answer::Int(157));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part2(&parsed), Answer::Int(70));
+    }
+
+    #[test]
+    fn rejects_rucksacks_without_shared_items() {
+        let error = |input: &str| Day3::parse(input).err().unwrap().locate(input).to_string();
+
+        assert_eq!(
+            error("abca\ncd\nef"),
+            "line 2, column 1: Both halves of a rucksack must share an item (found `cd`)"
+        );
+        assert_eq!(
+            error("abca\nbcdb\ncaac\naa\nbb\ncc"),
+            "line 4, column 1: The elves of a group must share a badge item (found `aa`)"
+        );
+    }
 }
@@ -1,12 +1,14 @@
 use std::{ops::RangeInclusive, str::FromStr};
 
-use crate::utils::get_input_content;
+use crate::{
+    error::{parse_lines, parse_number, ParseError},
+    solution::{Answer, Solution},
+};
 
-const INPUT_PATH: &str = "inputs/day_4.txt";
 const PAIR_DELIMITER: char = ',';
 
 // Pairs of sections
-struct Pair {
+pub struct Pair {
     section_1: RangeInclusive<u32>,
     section_2: RangeInclusive<u32>,
 }
@@ -39,67 +41,82 @@ impl Pair {
     }
 }
 
+impl Pair {
+    fn parse_section(section: &str) -> Result<RangeInclusive<u32>, ParseError> {
+        let (start, end) = section
+            .split_once('-')
+            .ok_or_else(|| ParseError::new("Expected a section like `2-4`", section))?;
+
+        Ok(RangeInclusive::new(
+            parse_number(start)?,
+            parse_number(end)?,
+        ))
+    }
+}
+
 impl FromStr for Pair {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (first, second) = s.split_once(PAIR_DELIMITER).unwrap();
-
-        let mut first = first.split('-').map(|i| i.parse::<u32>().unwrap());
-        let mut second = second.split('-').map(|i| i.parse::<u32>().unwrap());
+        let (first, second) = s
+            .split_once(PAIR_DELIMITER)
+            .ok_or_else(|| ParseError::new("Expected two sections separated by a comma", s))?;
 
         Ok(Self {
-            section_1: RangeInclusive::new(first.next().unwrap(), first.next().unwrap()),
-            section_2: RangeInclusive::new(second.next().unwrap(), second.next().unwrap()),
+            section_1: Self::parse_section(first)?,
+            section_2: Self::parse_section(second)?,
         })
     }
 }
 
-struct Challenge {
-    pairs: Vec<Pair>,
-}
+pub struct Day4;
 
-impl Challenge {
-    fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
-        Self {
-            pairs: input.lines().map(|line| line.parse().unwrap()).collect(),
-        }
+impl Solution for Day4 {
+    type Parsed = Vec<Pair>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
     }
 
-    fn process_task_1(&self) {
+    fn part1(pairs: &Self::Parsed) -> Answer {
         let overlaping_count =
-            self.pairs
+            pairs
                 .iter()
                 .fold(0, |acc, pair| match pair.is_there_complete_overlapping() {
                     true => acc + 1,
                     false => acc,
                 });
 
-        println!(
-            "Assigments that contains others fully: {}",
-            overlaping_count
-        );
+        overlaping_count.into()
     }
 
-    fn process_task_2(&self) {
-        let overlaping_count =
-            self.pairs
-                .iter()
-                .by_ref()
-                .fold(0, |acc, pair| match pair.is_there_overlap() {
-                    true => acc + 1,
-                    false => acc,
-                });
+    fn part2(pairs: &Self::Parsed) -> Answer {
+        let overlaping_count = pairs
+            .iter()
+            .fold(0, |acc, pair| match pair.is_there_overlap() {
+                true => acc + 1,
+                false => acc,
+            });
 
-        println!("Assigments that contains others: {}", overlaping_count);
+        overlaping_count.into()
     }
 }
 
-pub fn task_1() {
-    Challenge::new().process_task_1();
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-pub fn task_2() {
-    Challenge::new().process_task_2()
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part1(&parsed), Answer::Int(2));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part2(&parsed), Answer::Int(4));
+    }
 }
@@ -1,11 +1,12 @@
 use std::{collections::VecDeque, str::FromStr};
 
-use crate::utils::get_input_content;
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+};
 
-const INPUT_PATH: &str = "inputs/day_5.txt";
-
-#[derive(Debug)]
-struct StacksManager {
+#[derive(Debug, Clone)]
+pub struct StacksManager {
     stacks: Vec<VecDeque<char>>,
 }
 
@@ -60,68 +61,78 @@ impl StacksManager {
         }
     }
 
-    fn tops(&self) {
-        let tops =
-            self.stacks
-                .iter()
-                .fold(String::with_capacity(self.stacks.len()), |mut acc, item| {
-                    if let Some(c) = item.back() {
-                        acc.push(*c);
-                    }
-                    acc
-                });
-
-        println!("Tops of the stacks: {}", tops);
+    fn tops(&self) -> String {
+        self.stacks
+            .iter()
+            .fold(String::with_capacity(self.stacks.len()), |mut acc, item| {
+                if let Some(c) = item.back() {
+                    acc.push(*c);
+                }
+                acc
+            })
     }
 }
 
-#[derive(Debug)]
-struct Command {
+#[derive(Debug, Clone)]
+pub struct Command {
     count: usize,
     from: usize,
     to: usize,
 }
 
 impl FromStr for Command {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut pieces = s
-            .split_whitespace()
-            .filter_map(|item| item.parse::<usize>().ok());
-
-        Ok(Self {
-            count: pieces.next().unwrap(),
-            from: pieces.next().unwrap(),
-            to: pieces.next().unwrap(),
-        })
+        let pieces = s.split_whitespace().collect::<Vec<_>>();
+
+        match pieces[..] {
+            ["move", count, "from", from, "to", to] => Ok(Self {
+                count: parse_number(count)?,
+                from: parse_number(from)?,
+                to: parse_number(to)?,
+            }),
+            _ => Err(ParseError::new(
+                "Expected a command like `move 1 from 2 to 3`",
+                s,
+            )),
+        }
     }
 }
 
-#[derive(Debug)]
-struct Challenge {
+#[derive(Debug, Clone)]
+pub struct Challenge {
     manager: StacksManager,
     commands: Vec<Command>,
 }
 
 impl Challenge {
-    fn new() -> Self {
-        let input = get_input_content(INPUT_PATH);
+    fn new(input: &str) -> Result<Self, ParseError> {
         let mut input_lines = input.lines();
 
-        let first_line = input_lines.next().unwrap();
+        let first_line = input_lines
+            .next()
+            .ok_or_else(|| ParseError::new("Missing the stacks", input))?;
         let mut processed = Challenge::process_line(first_line);
 
         let mut manager = StacksManager::new(processed.len());
         manager.insert_into_stacks(&processed);
 
         loop {
-            let line = input_lines.next().unwrap();
+            let line = input_lines.next().ok_or_else(|| {
+                ParseError::new("Missing the line with the numbers of the stacks", input)
+            })?;
             if line.starts_with(" 1") {
                 break;
             }
 
             processed = Challenge::process_line(line);
+            if processed.len() > manager.stacks.len() {
+                return Err(ParseError::new(
+                    "The line has more stacks than the first one",
+                    line,
+                ));
+            }
             manager.insert_into_stacks(&processed);
         }
 
@@ -130,15 +141,23 @@ impl Challenge {
 
         let mut commands: Vec<Command> = vec![];
         for line in input_lines {
-            commands.push(line.parse::<Command>().unwrap());
+            let command = line.parse::<Command>()?;
+
+            if [command.from, command.to]
+                .iter()
+                .any(|stack| *stack == 0 || *stack > manager.stacks.len())
+            {
+                return Err(ParseError::new("The command uses an unknown stack", line));
+            }
+
+            commands.push(command);
         }
 
-        Self { manager, commands }
+        Ok(Self { manager, commands })
     }
 
     fn process_line(s: &str) -> Vec<char> {
-        s.replace('[', "")
-            .replace(']', "")
+        s.replace(['[', ']'], "")
             .replace("  ", " ")
             .chars()
             .collect::<Vec<char>>()
@@ -147,24 +166,54 @@ impl Challenge {
             .collect()
     }
 
-    fn task_1(&mut self) {
+    fn task_1(&mut self) -> String {
         for command in &self.commands {
             self.manager.process(command);
         }
-        self.manager.tops();
+        self.manager.tops()
     }
 
-    fn task_2(&mut self) {
+    fn task_2(&mut self) -> String {
         for command in &self.commands {
             self.manager.process_with_createmover_9001(command);
         }
-        self.manager.tops();
+        self.manager.tops()
     }
 }
 
-pub fn task_1() {
-    Challenge::new().task_1();
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = Challenge;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
+    }
+
+    fn part1(ch: &Self::Parsed) -> Answer {
+        ch.clone().task_1().into()
+    }
+
+    fn part2(ch: &Self::Parsed) -> Answer {
+        ch.clone().task_2().into()
+    }
 }
-pub fn task_2() {
-    Challenge::new().task_2();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part1(&parsed), Answer::from("CMZ"));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part2(&parsed), Answer::from("MCD"));
+    }
 }
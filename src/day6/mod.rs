@@ -1,18 +1,18 @@
 use std::collections::HashSet;
 
-use crate::utils::get_input_content;
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+};
 
-const INPUT_PATH: &str = "inputs/day_6.txt";
-
-struct Marker<'v> {
-    value: &'v str,
+pub struct Marker {
     end_idx: usize,
 }
 
 const PACKET_MARKER_SIZE: usize = 4;
 const MESSAGE_MARKER_SIZE: usize = 14;
 
-struct Challenge<'a> {
+pub struct Challenge<'a> {
     stream: &'a str,
 }
 
@@ -21,15 +21,15 @@ impl<'a> Challenge<'a> {
         Self { stream }
     }
 
-    fn start_of_packet(&self) -> Marker {
+    fn start_of_packet(&self) -> Option<Marker> {
         self.find_marker(PACKET_MARKER_SIZE)
     }
 
-    fn start_of_message(&self) -> Marker {
+    fn start_of_message(&self) -> Option<Marker> {
         self.find_marker(MESSAGE_MARKER_SIZE)
     }
 
-    fn find_marker(&self, marker_size: usize) -> Marker {
+    fn find_marker(&self, marker_size: usize) -> Option<Marker> {
         let position = self
             .stream
             .as_bytes()
@@ -37,31 +37,97 @@ impl<'a> Challenge<'a> {
             .position(|set| {
                 let hs: HashSet<&u8> = HashSet::from_iter(set);
                 hs.len() == marker_size
-            })
-            .unwrap();
+            })?;
 
-        Marker {
-            value: self.stream.get(position..position + marker_size).unwrap(),
+        Some(Marker {
             end_idx: position + marker_size,
-        }
+        })
     }
 
-    fn task_1(&self) {
-        let Marker { value, end_idx } = self.start_of_packet();
-        println!("The package marker '{value}' end on index: {end_idx}");
+    fn task_1(&self) -> usize {
+        let Marker { end_idx } = self
+            .start_of_packet()
+            .expect("The parser checked that the stream has markers");
+        end_idx
     }
 
-    fn task_2(&self) {
-        let Marker { value, end_idx } = self.start_of_message();
-        println!("The message marker '{value}' end on index: {end_idx}");
+    fn task_2(&self) -> usize {
+        let Marker { end_idx } = self
+            .start_of_message()
+            .expect("The parser checked that the stream has markers");
+        end_idx
     }
 }
 
-pub fn task_1() {
-    let input = get_input_content(INPUT_PATH);
-    Challenge::new(&input).task_1();
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let stream = input.trim();
+
+        if let Some((offset, c)) = stream.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(ParseError::new(
+                "The stream must only have lowercase letters",
+                &stream[offset..offset + c.len_utf8()],
+            ));
+        }
+
+        // A start-of-message marker holds a start-of-packet one
+        if Challenge::new(stream).start_of_message().is_none() {
+            return Err(ParseError::new(
+                "The stream has no start-of-message marker",
+                stream,
+            ));
+        }
+
+        Ok(stream.to_string())
+    }
+
+    fn part1(stream: &Self::Parsed) -> Answer {
+        Challenge::new(stream).task_1().into()
+    }
+
+    fn part2(stream: &Self::Parsed) -> Answer {
+        Challenge::new(stream).task_2().into()
+    }
 }
-pub fn task_2() {
-    let input = get_input_content(INPUT_PATH);
-    Challenge::new(&input).task_2();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part1(&parsed), Answer::Int(7));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part2(&parsed), Answer::Int(19));
+    }
+
+    #[test]
+    fn rejects_streams_without_markers() {
+        let error = |input: &str| Day6::parse(input).err().unwrap().locate(input).to_string();
+
+        assert_eq!(
+            error("abc"),
+            "line 1, column 1: The stream has no start-of-message marker (found `abc`)"
+        );
+        assert_eq!(
+            error("abcdefghijklm\n"),
+            "line 1, column 1: The stream has no start-of-message marker (found `abcdefghijklm`)"
+        );
+        assert_eq!(
+            error("abcdEfghijklmnop"),
+            "line 1, column 5: The stream must only have lowercase letters (found `E`)"
+        );
+        assert!(Day6::parse("").is_err());
+    }
 }
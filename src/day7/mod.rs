@@ -1,49 +1,61 @@
-use std::{cell::RefCell, num::ParseIntError, rc::Rc, str::FromStr};
+use std::{collections::BTreeMap, fmt::Display, ops::RangeBounds, str::FromStr};
 
-use crate::utils::get_input_content;
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+    utils::json::Json,
+};
 
-const INPUT_PATH: &str = "inputs/day_7.txt";
-
-const FILE_SYSTEM_SPACE: u64 = 70000000;
-const UPDATE_REQUIRED_SPACE: u64 = 30000000;
+// The disk and the update of the puzzle
+const DISK_SIZE: u64 = 70000000;
+const UPDATE_SIZE: u64 = 30000000;
 
 #[derive(Debug)]
-enum Filetype {
+pub enum Filetype {
     File { file_size: u64, filename: String },
     Folder(String),
 }
 
 impl FromStr for Filetype {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split_whitespace();
+        let missing_name = || ParseError::new("Missing the name", s);
+
         match split.next() {
-            Some("dir") => Ok(Filetype::Folder(split.next().unwrap().to_string())),
+            Some("dir") => Ok(Filetype::Folder(
+                split.next().ok_or_else(missing_name)?.to_string(),
+            )),
             Some(file_size) => Ok(Filetype::File {
-                file_size: file_size.parse::<u64>()?,
-                filename: split.next().unwrap().to_string(),
+                file_size: parse_number(file_size)?,
+                filename: split.next().ok_or_else(missing_name)?.to_string(),
             }),
-            _ => panic!("Input error."),
+            None => Err(ParseError::new("Expected a file or a directory", s)),
         }
     }
 }
 
 #[derive(Debug)]
-enum Command {
+pub enum Command {
     CD(String),
     LS,
 }
 
 impl FromStr for Command {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split_whitespace().skip(1);
         match split.next() {
-            Some("cd") => Ok(Command::CD(split.next().unwrap().to_string())),
+            Some("cd") => Ok(Command::CD(
+                split
+                    .next()
+                    .ok_or_else(|| ParseError::new("Missing the directory", s))?
+                    .to_string(),
+            )),
             Some("ls") => Ok(Command::LS),
-            _ => panic!("Input error."),
+            _ => Err(ParseError::new("Expected `cd` or `ls`", s)),
         }
     }
 }
@@ -52,183 +64,679 @@ fn is_command(line: &str) -> bool {
     line.starts_with('$')
 }
 
-#[derive(Debug)]
-struct FileSystemNode {
+// Index of a node in the filesystem
+pub type NodeId = usize;
+
+#[derive(Debug, Clone)]
+pub struct FileSystemNode {
     filename: String,
+    is_dir: bool,
+    // Total size of everything under it, for directories
     size: u64,
-    children: Vec<Rc<RefCell<FileSystemNode>>>,
-    parent: Option<Rc<RefCell<FileSystemNode>>>,
+    children: Vec<NodeId>,
+    parent: Option<NodeId>,
 }
 
 impl FileSystemNode {
-    pub fn new(filename: &str) -> Self {
+    pub fn new(filename: &str, is_dir: bool, parent: Option<NodeId>) -> Self {
         Self {
             filename: String::from(filename),
+            is_dir,
             size: 0,
             children: vec![],
-            parent: None,
+            parent,
+        }
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+// The directories and files rebuilt from a terminal log, which both parts query. Nodes live
+// in a Vec and point to each other by index, the root being the first one.
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    nodes: Vec<FileSystemNode>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self {
+            nodes: vec![FileSystemNode::new("/", true, None)],
+        }
+    }
+}
+
+impl FromStr for FileSystem {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fs = Self::default();
+        let mut current = FileSystem::ROOT;
+        let mut listing = false;
+
+        for line in s.lines() {
+            if is_command(line) {
+                listing = false;
+
+                match line.parse::<Command>()? {
+                    Command::LS => listing = true,
+                    Command::CD(path) => {
+                        current = fs
+                            .resolve(current, &path)
+                            .ok_or_else(|| ParseError::new("Unknown directory", line))?;
+                    }
+                }
+            } else {
+                if !listing {
+                    return Err(ParseError::new(
+                        "Expected a command before the listing",
+                        line,
+                    ));
+                }
+
+                let (name, is_dir, size) = match line.parse::<Filetype>()? {
+                    Filetype::Folder(folder_name) => (folder_name, true, 0),
+                    Filetype::File {
+                        filename,
+                        file_size,
+                    } => (filename, false, file_size),
+                };
+
+                if matches!(name.as_str(), "." | "..") || name.contains('/') {
+                    return Err(ParseError::new("Invalid name", line));
+                }
+
+                fs.add(current, &name, is_dir, size)
+                    .map_err(|message| ParseError::new(message, line))?;
+            }
         }
+
+        Ok(fs)
     }
+}
 
-    pub fn add_node(parent: Rc<RefCell<FileSystemNode>>, child: Rc<RefCell<FileSystemNode>>) {
-        child.borrow_mut().set_parent(Rc::clone(&parent));
-        parent.borrow_mut().add_child(child);
+impl FileSystem {
+    pub const ROOT: NodeId = 0;
+
+    pub fn node(&self, id: NodeId) -> &FileSystemNode {
+        &self.nodes[id]
     }
 
-    pub fn add_child(&mut self, node: Rc<RefCell<FileSystemNode>>) {
-        self.children.push(node);
+    // The size of everything in the filesystem
+    pub fn size(&self) -> u64 {
+        self.nodes[Self::ROOT].size
     }
 
-    pub fn set_parent(&mut self, parent: Rc<RefCell<FileSystemNode>>) {
-        self.parent = Some(Rc::clone(&parent));
+    // Follows `path` from the directory `from` like `cd` does: absolute paths start at the
+    // root, and `..` stays there
+    pub fn resolve(&self, from: NodeId, path: &str) -> Option<NodeId> {
+        let start = match path.starts_with('/') {
+            true => Self::ROOT,
+            false => from,
+        };
+
+        path.split('/')
+            .filter(|part| !part.is_empty())
+            .try_fold(start, |dir, part| match part {
+                "." => Some(dir),
+                ".." => Some(self.nodes[dir].parent.unwrap_or(Self::ROOT)),
+                name => self.child(dir, name).filter(|id| self.nodes[*id].is_dir),
+            })
     }
 
-    pub fn dirs_with_at_most_size(&self, at_most: u64) -> Vec<u64> {
-        let mut result = vec![];
-        if !self.children.is_empty() && self.size <= at_most {
-            result.push(self.size);
+    // The full path of a node, like `/a/e`
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = vec![];
+        let mut current = id;
+
+        while let Some(parent) = self.nodes[current].parent {
+            names.push(self.nodes[current].filename.as_str());
+            current = parent;
         }
 
-        self.children
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        self.nodes[dir]
+            .children
             .iter()
-            .flat_map(|child| child.borrow().dirs_with_at_most_size(at_most))
-            .for_each(|size| result.push(size));
+            .copied()
+            .find(|id| self.nodes[*id].filename == name)
+    }
+
+    // Adds an entry listed in `dir`. Listing a directory again doesn't change it, as long as
+    // it agrees with what was listed before.
+    fn add(
+        &mut self,
+        dir: NodeId,
+        name: &str,
+        is_dir: bool,
+        size: u64,
+    ) -> Result<NodeId, &'static str> {
+        if let Some(existing) = self.child(dir, name) {
+            let node = &self.nodes[existing];
+
+            return match (node.is_dir, is_dir) {
+                (true, true) => Ok(existing),
+                (false, false) if node.size == size => Ok(existing),
+                _ => Err("The entry doesn't match an earlier listing"),
+            };
+        }
+
+        let id = self.nodes.len();
+        let mut node = FileSystemNode::new(name, is_dir, Some(dir));
+        node.size = size;
+        self.nodes.push(node);
+        self.nodes[dir].children.push(id);
+
+        let mut ancestor = Some(dir);
+        while let Some(current) = ancestor {
+            self.nodes[current].size += size;
+            ancestor = self.nodes[current].parent;
+        }
+
+        Ok(id)
+    }
+
+    // Every directory with its full path and total size, like `du`, the largest first
+    pub fn du(&self) -> Vec<(String, u64)> {
+        self.dirs_in_range(..)
+    }
+
+    // The directories whose total size is in `range`, the largest first
+    pub fn dirs_in_range(&self, range: impl RangeBounds<u64>) -> Vec<(String, u64)> {
+        self.entries(true, |node| range.contains(&node.size))
+    }
+
+    // The `n` largest files, the largest first
+    pub fn largest_files(&self, n: usize) -> Vec<(String, u64)> {
+        let mut files = self.entries(false, |_| true);
+        files.truncate(n);
+        files
+    }
+
+    // The paths of the files matching `pattern`, in order. `*` stands for any characters
+    // but `/` and `?` for a single one. Patterns with a `/` are matched against the full
+    // path, the others against the name alone.
+    pub fn find(&self, pattern: &str) -> Vec<String> {
+        let mut paths = (0..self.nodes.len())
+            .filter(|id| !self.nodes[*id].is_dir)
+            .map(|id| self.path(id))
+            .filter(|path| match pattern.contains('/') {
+                true => glob_matches(pattern, path),
+                false => glob_matches(pattern, path.rsplit('/').next().unwrap_or_default()),
+            })
+            .collect::<Vec<_>>();
+
+        paths.sort();
+        paths
+    }
+
+    // The total size of the files by extension, files without one being under ""
+    pub fn size_by_extension(&self) -> BTreeMap<String, u64> {
+        let mut sizes = BTreeMap::new();
 
-        result
+        for file in self.nodes.iter().filter(|node| !node.is_dir) {
+            let extension = match file.filename.rsplit_once('.') {
+                Some((stem, extension)) if !stem.is_empty() => extension,
+                _ => "",
+            };
+            *sizes.entry(extension.to_string()).or_default() += file.size;
+        }
+
+        sizes
     }
 
-    pub fn dirs_with_at_least_size(&self, at_least: u64) -> Vec<u64> {
-        let mut result = vec![];
-        if !self.children.is_empty() && self.size >= at_least {
-            result.push(self.size);
+    // The smallest directory to delete so that a disk of `disk_size` has `required` bytes
+    // free, or None when even deleting everything isn't enough
+    pub fn dir_to_free(&self, disk_size: u64, required: u64) -> Option<(String, u64)> {
+        if required > disk_size {
+            return None;
         }
 
-        self.children
+        let unused = disk_size.saturating_sub(self.size());
+        let to_free = required.saturating_sub(unused);
+
+        self.dirs_in_range(to_free..).pop()
+    }
+
+    // The directories or files accepted by `filter`, with their paths, the largest first
+    fn entries(
+        &self,
+        dirs: bool,
+        mut filter: impl FnMut(&FileSystemNode) -> bool,
+    ) -> Vec<(String, u64)> {
+        let mut entries = (0..self.nodes.len())
+            .filter(|id| self.nodes[*id].is_dir == dirs && filter(&self.nodes[*id]))
+            .map(|id| (self.path(id), self.nodes[id].size))
+            .collect::<Vec<_>>();
+
+        entries.sort_by(|(path_a, size_a), (path_b, size_b)| {
+            size_b.cmp(size_a).then_with(|| path_a.cmp(path_b))
+        });
+        entries
+    }
+
+    // The `du` report as text, with a line per directory
+    pub fn du_report(&self) -> String {
+        self.du()
             .iter()
-            .flat_map(|child| child.borrow().dirs_with_at_least_size(at_least))
-            .for_each(|size| result.push(size));
+            .map(|(path, size)| format!("{size}\t{path}\n"))
+            .collect()
+    }
+
+    // The tree as nested JSON objects, children sorted by name. Directories have the total
+    // size of their contents, which `from_json` checks.
+    pub fn to_json(&self) -> Json {
+        self.node_json(Self::ROOT)
+    }
+
+    fn node_json(&self, id: NodeId) -> Json {
+        let node = &self.nodes[id];
+        let kind = match node.is_dir {
+            true => "dir",
+            false => "file",
+        };
+        let mut fields = vec![
+            ("name", Json::from(node.filename.as_str())),
+            ("type", Json::from(kind)),
+            ("size", Json::from(node.size as i64)),
+        ];
+
+        if node.is_dir {
+            let mut children = node.children.clone();
+            children.sort_by_key(|child| &self.nodes[*child].filename);
+
+            fields.push((
+                "children",
+                Json::Array(
+                    children
+                        .into_iter()
+                        .map(|child| self.node_json(child))
+                        .collect(),
+                ),
+            ));
+        }
+
+        Json::object(fields)
+    }
+
+    // Rebuilds a filesystem from the JSON written by `to_json`
+    pub fn from_json(s: &str) -> Result<Self, ParseError> {
+        let json = s.parse::<Json>()?;
+        let mut fs = Self::default();
+
+        if json.get("type").and_then(Json::as_str) != Some("dir") {
+            return Err(ParseError::new("The root must be a directory", ""));
+        }
+
+        fs.load_dir(Self::ROOT, &json)?;
+        Ok(fs)
+    }
+
+    fn load_dir(&mut self, id: NodeId, json: &Json) -> Result<(), ParseError> {
+        let path = self.path(id);
+        let error = |message: &str| ParseError::new(format!("{message} in `{path}`"), "");
+
+        let children = json
+            .get("children")
+            .and_then(Json::as_array)
+            .ok_or_else(|| error("Missing the children"))?;
+
+        for child in children {
+            let name = child
+                .get("name")
+                .and_then(Json::as_str)
+                .filter(|name| !matches!(*name, "" | "." | "..") && !name.contains('/'))
+                .ok_or_else(|| error("Missing or invalid name"))?;
+            let is_dir = match child.get("type").and_then(Json::as_str) {
+                Some("dir") => true,
+                Some("file") => false,
+                _ => return Err(error(&format!("Unknown type for `{name}`"))),
+            };
+            let size = match is_dir {
+                true => 0,
+                false => child
+                    .get("size")
+                    .and_then(Json::as_i64)
+                    .and_then(|size| u64::try_from(size).ok())
+                    .ok_or_else(|| error(&format!("Missing or invalid size for `{name}`")))?,
+            };
+
+            let child_id = self.add(id, name, is_dir, size).map_err(&error)?;
+
+            if is_dir {
+                self.load_dir(child_id, child)?;
+            }
+        }
+
+        match json.get("size").and_then(Json::as_i64) {
+            Some(size) if u64::try_from(size) != Ok(self.nodes[id].size) => {
+                Err(error("The size doesn't match the contents"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn write_tree(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        id: NodeId,
+        depth: usize,
+    ) -> std::fmt::Result {
+        let node = &self.nodes[id];
+        let kind = match node.is_dir {
+            true => "dir",
+            false => "file",
+        };
+        writeln!(
+            f,
+            "{:indent$}- {} ({kind}, size={})",
+            "",
+            node.filename,
+            node.size,
+            indent = depth * 2
+        )?;
+
+        let mut children = node.children.clone();
+        children.sort_by_key(|child| &self.nodes[*child].filename);
+
+        for child in children {
+            self.write_tree(f, child, depth + 1)?;
+        }
 
-        result
+        Ok(())
     }
+}
+
+// Whether `text` matches `pattern`, where `*` stands for any characters but `/` and `?` for a
+// single one
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let mut pattern_chars = pattern.chars();
 
-    pub fn update_folder_size(&mut self, size: u64) {
-        self.size += size;
-        if let Some(parent) = &self.parent {
-            parent.borrow_mut().update_folder_size(size);
+    match pattern_chars.next() {
+        None => text.is_empty(),
+        Some('*') => {
+            let rest = pattern_chars.as_str();
+            let end = text.find('/').unwrap_or(text.len());
+
+            (0..=end)
+                .filter(|idx| text.is_char_boundary(*idx))
+                .any(|idx| glob_matches(rest, &text[idx..]))
+        }
+        Some(expected) => {
+            let mut text_chars = text.chars();
+
+            match text_chars.next() {
+                Some(c) if c == expected || (expected == '?' && c != '/') => {
+                    glob_matches(pattern_chars.as_str(), text_chars.as_str())
+                }
+                _ => false,
+            }
         }
     }
 }
 
-struct Challenge {
-    data: String,
+// Writes the tree like the puzzle does, with the total size of every directory
+impl Display for FileSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_tree(f, Self::ROOT, 0)
+    }
 }
 
+pub struct Challenge;
+
 impl Challenge {
-    pub fn new() -> Self {
-        let data = get_input_content(INPUT_PATH);
-        Self { data }
-    }
-
-    pub fn process_data(&self) {
-        let _commands = self
-            .data
-            .lines()
-            .filter_map(|line| {
-                if is_command(line) {
-                    return line.parse::<Command>().ok();
-                }
-                None
-            })
-            .collect::<Vec<Command>>();
-
-        let root = Rc::new(RefCell::new(FileSystemNode::new("/")));
-        let current = Rc::clone(&root);
-
-        self.data.lines().skip(1).fold(
-            Rc::clone(&current),
-            |acc: Rc<RefCell<FileSystemNode>>, line| {
-                if is_command(line) {
-                    match line.parse::<Command>() {
-                        Ok(Command::LS) => {}
-                        Ok(Command::CD(dir)) => match dir.as_str() {
-                            ".." => match &acc.borrow().parent {
-                                None => panic!("This shouldn't happens"),
-                                Some(parent) => return Rc::clone(parent),
-                            },
-                            folder => {
-                                return Rc::clone(
-                                    acc.borrow()
-                                        .children
-                                        .iter()
-                                        .find(|child| child.borrow().filename == folder)
-                                        .unwrap(),
-                                );
-                            }
-                        },
-                        _ => {
-                            panic!("Error");
-                        }
-                    }
-                } else {
-                    match line.parse::<Filetype>() {
-                        Ok(Filetype::Folder(folder_name)) => {
-                            // Found folder, add it to the parent
-                            let new_node = Rc::new(RefCell::new(FileSystemNode::new(&folder_name)));
-                            FileSystemNode::add_node(Rc::clone(&acc), Rc::clone(&new_node));
-
-                            return acc;
-                        }
-                        Ok(Filetype::File {
-                            filename,
-                            file_size,
-                        }) => {
-                            let new_node = Rc::new(RefCell::new(FileSystemNode::new(&filename)));
-                            FileSystemNode::add_node(Rc::clone(&acc), Rc::clone(&new_node));
-
-                            new_node.borrow_mut().size = file_size;
-                            acc.borrow_mut().update_folder_size(file_size);
-
-                            return acc;
-                        }
-                        _ => {
-                            panic!("Error");
-                        }
-                    }
-                }
+    fn task_1(fs: &FileSystem) -> u64 {
+        fs.dirs_in_range(..=100000)
+            .iter()
+            .map(|(_, size)| size)
+            .sum()
+    }
+
+    fn task_2(fs: &FileSystem, disk_size: u64, update_size: u64) -> u64 {
+        let (_, size) = fs
+            .dir_to_free(disk_size, update_size)
+            .expect("No directory is big enough to make room for the update");
+
+        size
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = FileSystem;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(fs: &Self::Parsed) -> Answer {
+        Challenge::task_1(fs).into()
+    }
+
+    fn part2(fs: &Self::Parsed) -> Answer {
+        Challenge::task_2(fs, DISK_SIZE, UPDATE_SIZE).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part1(&parsed), Answer::Int(95437));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part2(&parsed), Answer::Int(24933642));
+    }
+
+    #[test]
+    fn prints_the_tree() {
+        let fs = Day7::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            fs.to_string(),
+            "\
+- / (dir, size=48381165)
+  - a (dir, size=94853)
+    - e (dir, size=584)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir, size=24933642)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+"
+        );
+    }
 
-                acc
-            },
+    #[test]
+    fn reports_directory_sizes() {
+        let fs = Day7::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            fs.du(),
+            vec![
+                (String::from("/"), 48381165),
+                (String::from("/d"), 24933642),
+                (String::from("/a"), 94853),
+                (String::from("/a/e"), 584),
+            ]
         );
+        assert_eq!(fs.du_report().lines().last(), Some("584\t/a/e"));
+    }
 
-        Self::task_1(Rc::clone(&root));
-        Self::task_2(Rc::clone(&root));
+    #[test]
+    fn resolves_paths() {
+        let log = "\
+$ cd /
+$ ls
+dir a
+$ cd a
+$ ls
+dir e
+10 f
+$ cd /a/e
+$ ls
+5 i
+$ cd ../..
+$ cd a/./e/../e
+$ ls
+5 i
+$ cd /
+$ cd ..
+$ ls
+dir a
+";
+        let fs = log.parse::<FileSystem>().unwrap();
+
+        assert_eq!(fs.size(), 15);
+        let e = fs.resolve(FileSystem::ROOT, "a/e").unwrap();
+        assert_eq!(fs.path(e), "/a/e");
+        assert_eq!(fs.node(e).size(), 5);
+        assert_eq!(fs.resolve(e, "../f"), None, "Files can't be entered");
+        assert_eq!(fs.resolve(e, "/"), Some(FileSystem::ROOT));
     }
 
-    fn task_1(root: Rc<RefCell<FileSystemNode>>) {
-        let sum = root
-            .borrow()
-            .dirs_with_at_most_size(100000)
-            .iter()
-            .sum::<u64>();
+    #[test]
+    fn rejects_inconsistent_logs() {
+        let error = |log: &str| log.parse::<FileSystem>().unwrap_err().to_string();
+
+        assert_eq!(
+            error("$ cd /\n$ cd a\n"),
+            "Unknown directory (found `$ cd a`)"
+        );
+        assert_eq!(
+            error("$ ls\n10 a\n$ ls\n20 a\n"),
+            "The entry doesn't match an earlier listing (found `20 a`)"
+        );
+        assert_eq!(
+            error("$ ls\ndir a\n$ ls\n5 a\n"),
+            "The entry doesn't match an earlier listing (found `5 a`)"
+        );
+        assert_eq!(
+            error("$ cd /\n10 a\n"),
+            "Expected a command before the listing (found `10 a`)"
+        );
+    }
 
-        println!("Sum of the folder with at least 100000: {}", sum);
+    #[test]
+    fn queries_files_and_directories() {
+        let fs = Day7::parse(EXAMPLE).unwrap();
+
+        assert_eq!(fs.find("*.log"), vec!["/d/d.log"]);
+        assert_eq!(fs.find("?"), vec!["/a/e/i", "/a/f", "/a/g", "/d/j", "/d/k"]);
+        assert_eq!(fs.find("/a/*"), vec!["/a/f", "/a/g", "/a/h.lst"]);
+        assert_eq!(fs.find("/*/*/*"), vec!["/a/e/i"]);
+        assert!(fs.find("*.txt.*").is_empty());
+
+        assert_eq!(
+            fs.largest_files(2),
+            vec![
+                (String::from("/b.txt"), 14848514),
+                (String::from("/c.dat"), 8504156)
+            ]
+        );
+        assert_eq!(
+            fs.dirs_in_range(500..100000),
+            vec![(String::from("/a"), 94853), (String::from("/a/e"), 584)]
+        );
+
+        let sizes = fs.size_by_extension();
+        assert_eq!(sizes[""], 584 + 29116 + 2557 + 4060174 + 7214296);
+        assert_eq!(sizes["txt"], 14848514);
+        assert_eq!(sizes.values().sum::<u64>(), fs.size());
     }
 
-    fn task_2(root: Rc<RefCell<FileSystemNode>>) {
-        let current_unused_space = FILE_SYSTEM_SPACE - root.borrow().size;
+    #[test]
+    fn finds_the_directory_to_delete() {
+        let fs = Day7::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            fs.dir_to_free(DISK_SIZE, UPDATE_SIZE),
+            Some((String::from("/d"), 24933642))
+        );
+        assert_eq!(
+            fs.dir_to_free(48381165, 90000),
+            Some((String::from("/a"), 94853))
+        );
+        assert_eq!(
+            fs.dir_to_free(70000000, 21619000),
+            Some((String::from("/a/e"), 584)),
+            "Only 165 bytes are missing"
+        );
+        assert_eq!(fs.dir_to_free(100, 200), None);
+    }
 
-        let mut folders = root
-            .borrow()
-            .dirs_with_at_least_size(UPDATE_REQUIRED_SPACE - current_unused_space);
+    #[test]
+    fn exports_and_imports_json() {
+        let fs = Day7::parse(EXAMPLE).unwrap();
+        let json = fs.to_json().to_string();
 
-        folders.sort();
+        assert!(json.starts_with(
+            r#"{"name":"/","type":"dir","size":48381165,"children":[{"name":"a","type":"dir","size":94853,"children":[{"name":"e","#
+        ));
 
-        println!("Size of the folder to delete: {}", folders[0]);
+        let loaded = FileSystem::from_json(&json).unwrap();
+        assert_eq!(loaded.to_string(), fs.to_string());
+        assert_eq!(loaded.du(), fs.du());
+        assert_eq!(loaded.to_json().to_string(), json);
+        assert_eq!(Challenge::task_1(&loaded), 95437);
     }
-}
 
-pub fn task_1() {
-    Challenge::new().process_data();
-}
+    #[test]
+    fn rejects_invalid_json_trees() {
+        let error = |json: &str| FileSystem::from_json(json).unwrap_err().to_string();
 
-pub fn task_2() {}
+        assert_eq!(
+            error(r#"{"name":"/","type":"file","size":1}"#),
+            "The root must be a directory"
+        );
+        assert_eq!(
+            error(r#"{"name":"/","type":"dir","children":[{"name":"a","type":"dir"}]}"#),
+            "Missing the children in `/a`"
+        );
+        assert_eq!(
+            error(r#"{"name":"/","type":"dir","children":[{"name":"a","type":"file","size":-1}]}"#),
+            "Missing or invalid size for `a` in `/`"
+        );
+        assert_eq!(
+            error(
+                r#"{"name":"/","type":"dir","size":2,"children":[{"name":"a","type":"file","size":1}]}"#
+            ),
+            "The size doesn't match the contents in `/`"
+        );
+        assert_eq!(
+            error(
+                r#"{"name":"/","type":"dir","children":[{"name":"a","type":"file","size":1},{"name":"a","type":"dir","children":[]}]}"#
+            ),
+            "The entry doesn't match an earlier listing in `/`"
+        );
+    }
+}
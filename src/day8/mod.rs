@@ -1,17 +1,24 @@
-use std::{fmt::Display, num::ParseIntError, str::FromStr};
-
-use crate::utils::get_input_content;
-
-const INPUT_PATH: &str = "inputs/day_8.txt";
-
-enum VisibilityDirection {
-    Left,
-    Right,
-    Up,
-    Down,
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+    utils::{
+        color::on_rgb,
+        grid::{Grid, Pos},
+        image::{self, gray, Rgb, RED},
+    },
+};
+
+// What the heatmaps of the forest show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    Height,
+    Visibility,
+    ScenicScore,
 }
 
-struct Tree {
+pub struct Tree {
     value: u8,
     visible: bool,
     view_distance: u32,
@@ -19,11 +26,7 @@ struct Tree {
 
 impl Display for Tree {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            " {}({}, {}) ",
-            self.value, self.visible, self.view_distance
-        )
+        write!(f, "{}", self.value)
     }
 }
 
@@ -37,196 +40,355 @@ impl Clone for Tree {
     }
 }
 
-impl FromStr for Tree {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            value: s.parse()?,
-            visible: false,
-            view_distance: 0,
-        })
+impl TryFrom<char> for Tree {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value.to_digit(10) {
+            Some(height) => Ok(Self {
+                value: height as u8,
+                visible: false,
+                view_distance: 0,
+            }),
+            None => Err(value),
+        }
     }
 }
 
-struct Map {
-    width: usize,
-    height: usize,
-    layers: Vec<Vec<Tree>>,
+pub struct Map {
+    trees: Grid<Tree>,
 }
 
 impl FromStr for Map {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let layers: Vec<Vec<Tree>> = s
-            .lines()
-            .enumerate()
-            .map(|(index, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(idx, i)| {
-                        let mut t = format!("{i}").parse::<Tree>().unwrap();
-                        if index == 0 || index + 1 == line.len() {
-                            t.visible = true;
-                        }
-                        if idx == 0 || idx + 1 == line.len() {
-                            t.visible = true;
-                        }
-                        t
-                    })
-                    .collect()
-            })
-            .collect();
+        let trees = Grid::parse(s, |c| {
+            Tree::try_from(c).map_err(|_| "Tree heights must be a single digit")
+        })?;
 
-        Ok(Self {
-            width: layers[0].len(),
-            height: layers[0].len(),
-            layers,
-        })
+        Ok(Self { trees })
     }
 }
 
+// Writes the heights of the trees, like the input
 impl Display for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let map_as_str = self.layers.iter().flatten().enumerate().fold(
-            String::new(),
-            |mut acc, (index, item)| {
-                if index != 0 && index % self.width == 0 {
-                    acc.push('\n');
-                }
-                acc.push_str(&item.to_string());
-                acc
-            },
-        );
-
-        write!(f, "{}", map_as_str)
+        write!(f, "{}", self.trees)
     }
 }
 
 impl Map {
+    // Looks along every row and column in both directions, which is linear in the number of
+    // trees
     pub fn update_trees_visibility(&mut self) {
-        for i in 1..self.height - 1 {
-            for j in 1..self.width - 1 {
-                // From the left
-                let mut view_distance_left = 0;
-                let tree = &self.layers[i][j];
-                let any = self.layers[i][..j].iter().rev().all(|other_tree| {
-                    let is_shorter = other_tree.value < tree.value;
-                    view_distance_left += 1;
-                    is_shorter
-                });
-
-                if any {
-                    self.layers[i][j].visible = any;
-                }
+        for tree in self.trees.iter_mut() {
+            tree.visible = false;
+            tree.view_distance = 1;
+        }
 
-                // From the right
-                let mut view_distance_right = 0;
-                let tree = &self.layers[i][j];
-                let any = self.layers[i][j + 1..].iter().all(|other_tree| {
-                    let is_shorter = other_tree.value < tree.value;
-                    view_distance_right += 1;
-                    is_shorter
-                });
-
-                if any {
-                    self.layers[i][j].visible = any;
-                }
+        let (width, height) = (self.trees.width(), self.trees.height());
+        for y in 0..height {
+            self.sweep((0..width).map(|x| (x, y)));
+            self.sweep((0..width).rev().map(|x| (x, y)));
+        }
+        for x in 0..width {
+            self.sweep((0..height).map(|y| (x, y)));
+            self.sweep((0..height).rev().map(|y| (x, y)));
+        }
+    }
 
-                // From top
-                let mut view_distance_top = 0;
-                let tree = &self.layers[i][j];
-                let any = self.layers[..i].iter().rev().all(|other_tree| {
-                    let is_shorter = other_tree[j].value < tree.value;
-                    view_distance_top += 1;
-                    is_shorter
-                });
-
-                if any {
-                    self.layers[i][j].visible = any;
-                }
+    // The tree with the highest scenic score, the first one row by row on ties. There is none
+    // when no tree sees anything, as on the edges.
+    pub fn best_spot(&self) -> Option<Pos> {
+        let best = self
+            .trees
+            .iter()
+            .map(|tree| tree.view_distance)
+            .max()
+            .filter(|best| *best > 0)?;
+        self.trees.position(|tree| tree.view_distance == best)
+    }
 
-                // From bottom
-                let mut view_distance_bottom = 0;
-                let tree = &self.layers[i][j];
-                let any = self.layers[i + 1..].iter().all(|other_tree| {
-                    let is_shorter = other_tree[j].value < tree.value;
-                    view_distance_bottom += 1;
-                    is_shorter
-                });
-
-                if any {
-                    self.layers[i][j].visible = any;
-                }
+    // How bright every tree is in `layer`, from 0 to 255. Scenic scores are log-scaled, as the
+    // best ones are orders of magnitude above the rest.
+    pub fn heatmap(&self, layer: Layer) -> Grid<u8> {
+        let best = self.best_spot().map(|pos| self.trees[pos].view_distance);
+        let scale = f64::from(best.unwrap_or_default()).ln_1p();
+
+        self.trees.map(|_, tree| match layer {
+            Layer::Height => (u32::from(tree.value) * 255 / 9) as u8,
+            Layer::Visibility => match tree.visible {
+                true => 255,
+                false => 0,
+            },
+            Layer::ScenicScore if scale > 0.0 => {
+                (f64::from(tree.view_distance).ln_1p() / scale * 255.0).round() as u8
+            }
+            Layer::ScenicScore => 0,
+        })
+    }
 
-                let view_distance = [
-                    view_distance_top,
-                    view_distance_left,
-                    view_distance_bottom,
-                    view_distance_right,
-                ]
-                .iter()
-                .map(|i| if *i == 0 { 1 } else { *i })
-                .product();
+    // The heatmap in shades of grey, with the best spot in red
+    pub fn colored_heatmap(&self, layer: Layer) -> Grid<Rgb> {
+        let best = self.best_spot();
+
+        self.heatmap(layer)
+            .map(|pos, value| match Some(pos) == best {
+                true => RED,
+                false => gray(*value),
+            })
+    }
+
+    // The heatmap as a greyscale image, which can't show the best spot
+    pub fn to_pgm(&self, layer: Layer) -> String {
+        image::pgm(&self.heatmap(layer))
+    }
+
+    pub fn to_ppm(&self, layer: Layer) -> String {
+        image::ppm(&self.colored_heatmap(layer))
+    }
 
-                // Update view distance of the current tree
-                self.layers[i][j].view_distance = view_distance;
+    // The heatmap for a terminal, two columns per tree so they look square
+    pub fn to_ansi(&self, layer: Layer) -> String {
+        let mut text = String::new();
+
+        for row in self.colored_heatmap(layer).rows() {
+            for color in row {
+                text.push_str(&on_rgb("  ", *color));
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+
+    // Has every tree of `line` look back towards its start. The stack keeps the trees that can
+    // still block a view, getting shorter towards the top, so each tree is pushed and popped
+    // once. Trees on the edge see nothing, which zeroes their score.
+    fn sweep(&mut self, line: impl Iterator<Item = Pos>) {
+        let mut blockers: Vec<(usize, u8)> = vec![];
+
+        for (idx, pos) in line.enumerate() {
+            let tree = &mut self.trees[pos];
+
+            while blockers
+                .last()
+                .is_some_and(|(_, height)| *height < tree.value)
+            {
+                blockers.pop();
+            }
+
+            match blockers.last() {
+                Some((blocker, _)) => tree.view_distance *= (idx - blocker) as u32,
+                None => {
+                    tree.visible = true;
+                    tree.view_distance *= idx as u32;
+                }
             }
+
+            blockers.push((idx, tree.value));
         }
     }
 }
 
-struct Challenge {
+pub struct Challenge {
     data: Map,
 }
 
 impl Challenge {
-    pub fn new() -> Self {
-        let s = get_input_content(INPUT_PATH);
-
-        let mut data = s.parse::<Map>().unwrap();
+    pub fn new(s: &str) -> Result<Self, ParseError> {
+        let mut data = s.parse::<Map>()?;
         data.update_trees_visibility();
 
-        Self { data }
+        Ok(Self { data })
     }
 
-    pub fn task_1(&mut self) {
-        let visible_trees = self
-            .data
-            .layers
-            .iter()
-            .flatten()
-            .filter(|tree| tree.visible)
-            .count();
-
-        println!("Number of visible trees: {visible_trees}");
+    pub fn task_1(&self) -> usize {
+        self.data.trees.iter().filter(|tree| tree.visible).count()
     }
 
-    pub fn task_2(&self) {
-        // self.print_map();
-
+    pub fn task_2(&self) -> u32 {
         let max = self
             .data
-            .layers
+            .trees
             .iter()
-            .flatten()
             .max_by(|a, b| a.view_distance.cmp(&b.view_distance));
 
-        println!("Best scenic score: {}", max.unwrap().view_distance);
+        max.unwrap().view_distance
     }
 
-    pub fn print_map(&self) {
-        println!("{}", self.data);
+    pub fn map(&self) -> &Map {
+        &self.data
     }
 }
 
-pub fn task_1() {
-    let mut ch = Challenge::new();
-    ch.task_1();
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = Challenge;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Challenge::new(input)
+    }
+
+    fn part1(ch: &Self::Parsed) -> Answer {
+        ch.task_1().into()
+    }
+
+    fn part2(ch: &Self::Parsed) -> Answer {
+        ch.task_2().into()
+    }
 }
 
-pub fn task_2() {
-    let ch = Challenge::new();
-    ch.task_2();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::geom::ORTHOGONAL;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part1(&parsed), Answer::Int(21));
+    }
+
+    #[test]
+    fn part2_example() {
+        let parsed = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part2(&parsed), Answer::Int(8));
+    }
+
+    fn answers(input: &str) -> (Answer, Answer) {
+        let parsed = Day8::parse(input).unwrap();
+        (Day8::part1(&parsed), Day8::part2(&parsed))
+    }
+
+    #[test]
+    fn rectangular_forests() {
+        let wide = "30373\n25512\n65332";
+        let tall = "326\n055\n353\n713\n322";
+
+        assert_eq!(answers(wide), (Answer::Int(14), Answer::Int(2)));
+        assert_eq!(answers(tall), (Answer::Int(14), Answer::Int(2)));
+        assert_eq!(answers("30373"), (Answer::Int(5), Answer::Int(0)));
+        assert_eq!(answers("3\n0\n3\n7\n3"), (Answer::Int(5), Answer::Int(0)));
+        assert_eq!(answers("5"), (Answer::Int(1), Answer::Int(0)));
+    }
+
+    #[test]
+    fn rejects_malformed_forests() {
+        let error = |input: &str| Day8::parse(input).err().unwrap().to_string();
+
+        assert_eq!(
+            error("303\n25\n653"),
+            "All rows must have the same length (found `25`)"
+        );
+        assert_eq!(
+            error("303\n2a5"),
+            "Tree heights must be a single digit (found `a`)"
+        );
+        assert_eq!(error(""), "The grid is empty");
+    }
+
+    #[test]
+    fn renders_heatmaps() {
+        let parsed = Day8::parse(EXAMPLE).unwrap();
+        let map = parsed.map();
+
+        assert_eq!(map.to_string(), EXAMPLE.trim_end());
+        assert_eq!(map.best_spot(), Some((2, 3)));
+
+        let heights = map.heatmap(Layer::Height);
+        assert_eq!((heights[(3, 0)], heights[(1, 0)]), (198, 0));
+        let visible = map.heatmap(Layer::Visibility);
+        assert_eq!((visible[(0, 0)], visible[(3, 1)]), (255, 0));
+        let scores = map.heatmap(Layer::ScenicScore);
+        assert_eq!(
+            (scores[(2, 3)], scores[(1, 1)], scores[(0, 2)]),
+            (255, 80, 0)
+        );
+
+        assert!(map
+            .to_pgm(Layer::Height)
+            .starts_with("P2\n5 5\n255\n85 0 85 198 85\n"));
+        let ppm = map.to_ppm(Layer::Visibility);
+        assert!(ppm.starts_with("P3\n5 5\n255\n255 255 255 "));
+        let colors = map.colored_heatmap(Layer::Visibility);
+        assert_eq!(colors.iter().filter(|color| **color == RED).count(), 1);
+        assert_eq!(colors[(2, 3)], RED);
+
+        let ansi = map.to_ansi(Layer::ScenicScore);
+        assert_eq!(ansi.lines().count(), 5);
+        assert_eq!(ansi.matches("\x1b[48;2;255;0;0m").count(), 1);
+    }
+
+    #[test]
+    fn has_no_best_spot_without_a_view() {
+        let parsed = Day8::parse("30373").unwrap();
+        let map = parsed.map();
+
+        assert_eq!(map.best_spot(), None);
+        assert!(map
+            .heatmap(Layer::ScenicScore)
+            .iter()
+            .all(|value| *value == 0));
+        let colors = map.colored_heatmap(Layer::ScenicScore);
+        assert!(colors.iter().all(|color| *color != RED));
+    }
+
+    // The scan of every direction from every tree that the sweeps replaced
+    fn scan(map: &Map) -> Vec<(bool, u32)> {
+        map.trees
+            .iter_positions()
+            .map(|(pos, tree)| {
+                let mut visible = false;
+                let view_distance = ORTHOGONAL
+                    .into_iter()
+                    .map(|offset| {
+                        let mut view_distance = 0;
+                        let all_shorter = map.trees.ray(pos, offset).all(|pos| {
+                            view_distance += 1;
+                            map.trees[pos].value < tree.value
+                        });
+
+                        visible |= all_shorter;
+                        view_distance
+                    })
+                    .product();
+
+                (visible, view_distance)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sweeps_match_the_scan() {
+        // A forest with random heights, from a linear congruential generator
+        let mut seed = 17_u32;
+        let forest = (0..40)
+            .map(|_| {
+                (0..55)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                        char::from(b'0' + (seed >> 16) as u8 % 10)
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        for input in [EXAMPLE, forest.as_str()] {
+            let mut map = input.parse::<Map>().unwrap();
+            map.update_trees_visibility();
+
+            let swept = map
+                .trees
+                .iter()
+                .map(|tree| (tree.visible, tree.view_distance))
+                .collect::<Vec<_>>();
+            assert_eq!(swept, scan(&map));
+        }
+    }
 }
@@ -1,191 +1,374 @@
-use std::{collections::HashSet, num::ParseIntError, str::FromStr};
+use std::{collections::HashSet, fs, io, path::Path, str::FromStr};
 
-use crate::utils::get_input_content;
+use crate::{
+    error::{parse_lines, parse_number, ParseError},
+    solution::{Answer, Solution},
+    utils::{
+        geom::{Vec2, Vec3},
+        grid::Grid,
+        image::{self, gray, BLACK, RED},
+    },
+};
 
-const INPUT_PATH: &str = "inputs/day_9.txt";
+// The step of a move made of one or more of R, L, U and D on the puzzle's grid, like `R` or
+// `UR`, and F and B for forward and backward along z
+fn parse_offset(s: &str) -> Result<Position, ParseError> {
+    if s.is_empty() {
+        return Err(ParseError::new("Expected a direction", s));
+    }
 
-enum Dir {
-    Right,
-    Left,
-    Up,
-    Down,
-}
+    let mut offset = Position::default();
 
-impl FromStr for Dir {
-    type Err = ();
+    for (idx, c) in s.char_indices() {
+        let text = &s[idx..idx + c.len_utf8()];
+        let step = match c {
+            'R' => Position::new(1, 0, 0),
+            'L' => Position::new(-1, 0, 0),
+            'U' => Position::new(0, -1, 0),
+            'D' => Position::new(0, 1, 0),
+            'F' => Position::new(0, 0, 1),
+            'B' => Position::new(0, 0, -1),
+            _ => return Err(ParseError::new("Expected R, L, U, D, F or B", text)),
+        };
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.chars().next() {
-            Some('R') => Ok(Self::Right),
-            Some('L') => Ok(Self::Left),
-            Some('U') => Ok(Self::Up),
-            Some('D') => Ok(Self::Down),
-            _ => panic!("Input error"),
+        let axes = [(offset.x, step.x), (offset.y, step.y), (offset.z, step.z)];
+        if axes
+            .iter()
+            .any(|(used, stepped)| *used != 0 && *stepped != 0)
+        {
+            return Err(ParseError::new("A move can't go twice along an axis", text));
         }
+
+        offset += step;
     }
+
+    Ok(offset)
 }
 
-struct Movement(Dir, u8);
+pub struct Movement(Position, u8);
 
 impl FromStr for Movement {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dir, amount) = s.split_at(1);
-        Ok(Self(dir.parse().unwrap(), amount.trim().parse()?))
+        let (dir, amount) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new("Expected a movement like `R 4`", s))?;
+        Ok(Self(parse_offset(dir)?, parse_number(amount)?))
     }
 }
 
-#[derive(Eq, PartialEq, Hash, Clone, Debug)]
-struct Position(i32, i32);
+// Ropes are flat unless they move forward or backward, which makes them 3D
+pub type Position = Vec3;
 
-impl Position {
-    pub fn do_move(&mut self, m: &Movement) {
-        match m.0 {
-            Dir::Right => self.0 += 1,
-            Dir::Left => self.0 -= 1,
-            Dir::Up => self.1 += 1,
-            Dir::Down => self.1 -= 1,
-        }
+// Moves `knot` to keep touching `other`, the knot before it, sides and corners included:
+// when they're apart, it takes a step towards `other` along every axis
+pub fn follow(knot: &mut Position, other: &Position) {
+    if knot.chebyshev(*other) > 1 {
+        *knot += (*other - *knot).signum();
     }
-    pub fn follow(&mut self, other: &Position) {
-        // same position
-        if self.0 == other.0 && self.1 == other.1 {
-            return;
-        }
+}
 
-        // other moved to right
-        if self.1 == other.1 && other.0 - self.0 > 1 {
-            self.0 += 1;
-            return;
+// The position of every knot after each step, the head first, to look at how a rope moves.
+// The first frame is the initial state.
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    frames: Vec<Vec<Position>>,
+}
+
+impl Recorder {
+    pub fn frames(&self) -> &[Vec<Position>] {
+        &self.frames
+    }
+
+    // The top left and bottom right corners of the smallest box holding the start and every
+    // knot of every frame, so that all the frames have the same size. Frames are drawn seen
+    // along z, which hides the depth of 3D ropes.
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        self.frames
+            .iter()
+            .flatten()
+            .fold((Vec2::default(), Vec2::default()), |(min, max), knot| {
+                (
+                    Vec2::new(min.x.min(knot.x), min.y.min(knot.y)),
+                    Vec2::new(max.x.max(knot.x), max.y.max(knot.y)),
+                )
+            })
+    }
+
+    // Draws a frame of `knots` within the bounds, with `f` giving the cell of each knot by its
+    // index and `start` the cell of the start. Knots hide the ones after them.
+    fn draw<T: Clone>(
+        &self,
+        knots: &[Position],
+        empty: T,
+        start: T,
+        mut f: impl FnMut(usize) -> T,
+    ) -> Grid<T> {
+        let (min, max) = self.bounds();
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let cell = |pos: Position| ((pos.x - min.x) as usize, (pos.y - min.y) as usize);
+
+        let mut grid = Grid::new(width, height, empty);
+        grid[cell(Position::default())] = start;
+
+        for (idx, knot) in knots.iter().enumerate().rev() {
+            grid[cell(*knot)] = f(idx);
         }
 
-        // other moved to left
-        if self.1 == other.1 && self.0 - other.0 > 1 {
-            self.0 -= 1;
-            return;
+        grid
+    }
+
+    // Draws a frame like the puzzle does: `H` for the head, `T` for the tail of a rope of two
+    // knots and numbers for the knots of longer ones, `s` for the start
+    pub fn render(&self, frame: usize) -> String {
+        let knots = &self.frames[frame];
+        let label = |idx: usize| match (idx, knots.len()) {
+            (0, _) => 'H',
+            (_, 2) => 'T',
+            (idx, _) => char::from_digit(idx as u32 % 36, 36).unwrap_or('?'),
+        };
+
+        self.draw(knots, '.', 's', label).to_string()
+    }
+
+    // A frame as a PPM image, with the head in red and `scale` pixels for each side of a cell
+    pub fn to_ppm(&self, frame: usize, scale: usize) -> String {
+        let knots = &self.frames[frame];
+        let cells = self.draw(knots, BLACK, gray(96), |idx| match idx {
+            0 => RED,
+            _ => gray(255),
+        });
+        let pixels = Grid::from_fn(cells.width() * scale, cells.height() * scale, |(x, y)| {
+            cells[(x / scale, y / scale)]
+        });
+
+        image::ppm(&pixels)
+    }
+
+    // Writes every frame as a PPM image in `dir`, named after its number
+    pub fn write_ppm_frames(&self, dir: &Path, scale: usize) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+
+        for frame in 0..self.frames.len() {
+            let path = dir.join(format!("frame_{frame:05}.ppm"));
+            fs::write(path, self.to_ppm(frame, scale))?;
         }
 
-        if self.1.abs_diff(other.1) > 1 {
-            // self is below
-            if self.1 < other.1 {
-                match self.0.cmp(&other.0) {
-                    std::cmp::Ordering::Less => {
-                        self.0 += 1;
-                        self.1 += 1;
-                    }
-                    std::cmp::Ordering::Equal => {
-                        self.1 += 1;
-                    }
-                    std::cmp::Ordering::Greater => {
-                        self.0 -= 1;
-                        self.1 += 1;
-                    }
-                }
-            } else {
-                // self is above
-                match self.0.cmp(&other.0) {
-                    std::cmp::Ordering::Less => {
-                        self.0 += 1;
-                        self.1 -= 1;
-                    }
-                    std::cmp::Ordering::Equal => {
-                        self.1 -= 1;
-                    }
-                    std::cmp::Ordering::Greater => {
-                        self.0 -= 1;
-                        self.1 -= 1;
-                    }
-                }
-            }
+        Ok(())
+    }
+}
+
+pub struct Challenge<'a> {
+    steps: &'a [Movement],
+    recorder: Option<Recorder>,
+}
+
+impl<'a> Challenge<'a> {
+    pub fn new(steps: &'a [Movement]) -> Self {
+        Self {
+            steps,
+            recorder: None,
         }
+    }
 
-        if self.1.abs_diff(other.1) == 1 {
-            if self.0 == other.0 || self.0.abs_diff(other.0) == 1 {
-                return;
-            }
+    // Records every step of the executions, each one replacing the frames of the previous one
+    pub fn with_recorder(mut self) -> Self {
+        self.recorder = Some(Recorder::default());
+        self
+    }
 
-            if self.1 < other.1 {
-                if self.0 < other.0 {
-                    self.0 += 1;
-                    self.1 += 1;
-                } else {
-                    self.0 -= 1;
-                    self.1 += 1;
+    pub fn recorder(&self) -> Option<&Recorder> {
+        self.recorder.as_ref()
+    }
+
+    pub fn execute(&mut self) -> usize {
+        self.tail_visits(2)
+    }
+
+    pub fn execute_10_knots(&mut self) -> usize {
+        self.tail_visits(10)
+    }
+
+    fn tail_visits(&mut self, knots_count: usize) -> usize {
+        self.execute_generic(knots_count)
+            .last()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // Moves a rope of `knots_count` knots, returning how many positions each knot visited,
+    // the head first
+    pub fn execute_generic(&mut self, knots_count: usize) -> Vec<usize> {
+        assert!(knots_count > 0, "A rope needs at least one knot");
+
+        let mut knots = vec![Position::default(); knots_count];
+        let mut visited = vec![HashSet::from([Position::default()]); knots_count];
+        if let Some(recorder) = &mut self.recorder {
+            recorder.frames.clear();
+            recorder.frames.push(knots.clone());
+        }
+
+        for m in self.steps {
+            for _ in 0..m.1 {
+                knots[0] += m.0;
+                for index in 1..knots.len() {
+                    let prev = knots[index - 1];
+                    follow(&mut knots[index], &prev);
                 }
-                return;
-            }
 
-            if self.1 > other.1 {
-                if self.0 < other.0 {
-                    self.0 += 1;
-                    self.1 -= 1;
-                } else {
-                    self.0 -= 1;
-                    self.1 -= 1;
+                for (knot, visited) in knots.iter().zip(visited.iter_mut()) {
+                    visited.insert(*knot);
+                }
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.frames.push(knots.clone());
                 }
             }
         }
+
+        visited.iter().map(HashSet::len).collect()
     }
 }
 
-struct Challenge {
-    steps: String,
-    tail_unique_positions: HashSet<Position>,
-}
+pub struct Day9;
 
-impl Challenge {
-    pub fn new() -> Self {
-        Self {
-            steps: get_input_content(INPUT_PATH),
-            tail_unique_positions: HashSet::default(),
-        }
+impl Solution for Day9 {
+    type Parsed = Vec<Movement>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
     }
 
-    pub fn execute(&mut self) {
-        self.execute_generic(2);
+    fn part1(steps: &Self::Parsed) -> Answer {
+        Challenge::new(steps).execute().into()
     }
 
-    pub fn execute_10_knots(&mut self) {
-        self.execute_generic(10);
+    fn part2(steps: &Self::Parsed) -> Answer {
+        Challenge::new(steps).execute_10_knots().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    const LARGER_EXAMPLE: &str = include_str!("example_larger.txt");
+
+    #[test]
+    fn part1_example() {
+        let parsed = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part1(&parsed), Answer::Int(13));
     }
 
-    pub fn execute_generic(&mut self, knots_count: usize) {
-        let mut knots = vec![Position(0, 0); knots_count];
-        self.tail_unique_positions.insert(Position(0, 0));
+    #[test]
+    fn part2_example() {
+        let parsed = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part2(&parsed), Answer::Int(1));
+    }
 
-        self.steps
-            .lines()
-            .map(|s| s.parse::<Movement>().unwrap())
-            .for_each(|m| {
-                (0..m.1).for_each(|_| {
-                    for index in 0..knots.len() {
-                        match index {
-                            0 => {
-                                knots[0].do_move(&m);
-                            }
-                            _ => {
-                                let prev = knots[index - 1].clone();
-                                knots[index].follow(&prev);
-                            }
-                        }
-                    }
+    #[test]
+    fn part2_larger_example() {
+        let parsed = Day9::parse(LARGER_EXAMPLE).unwrap();
+        assert_eq!(Day9::part2(&parsed), Answer::Int(36));
+    }
 
-                    self.tail_unique_positions
-                        .insert(knots.last().unwrap().clone());
-                });
-            });
+    #[test]
+    fn records_frames() {
+        let parsed = Day9::parse(EXAMPLE).unwrap();
+        let mut challenge = Challenge::new(&parsed).with_recorder();
+        challenge.execute();
+        let recorder = challenge.recorder().unwrap();
 
-        println!(
-            "Unique positions with {knots_count} knots: {}",
-            self.tail_unique_positions.len()
+        assert_eq!(recorder.frames().len(), 25);
+        assert_eq!(recorder.bounds(), (Vec2::new(0, -4), Vec2::new(5, 0)));
+        assert_eq!(recorder.render(0), "......\n......\n......\n......\nH.....");
+        assert_eq!(recorder.render(4), "......\n......\n......\n......\ns..TH.");
+        assert_eq!(
+            recorder.render(24),
+            "......\n......\n.TH...\n......\ns....."
         );
+
+        let ppm = recorder.to_ppm(24, 2);
+        assert!(ppm.starts_with("P3\n12 10\n255\n"));
+        let samples = ppm.lines().skip(3).flat_map(str::split_whitespace);
+        assert_eq!(samples.count(), 12 * 10 * 3);
     }
-}
 
-pub fn task_1() {
-    Challenge::new().execute();
-}
-pub fn task_2() {
-    Challenge::new().execute_10_knots();
+    #[test]
+    fn records_only_the_last_execution() {
+        let parsed = Day9::parse(EXAMPLE).unwrap();
+        let mut challenge = Challenge::new(&parsed).with_recorder();
+        challenge.execute();
+        challenge.execute_10_knots();
+        let recorder = challenge.recorder().unwrap();
+
+        assert_eq!(recorder.frames().len(), 25);
+        assert!(recorder.frames().iter().all(|knots| knots.len() == 10));
+    }
+
+    #[test]
+    fn labels_the_knots_of_long_ropes() {
+        let parsed = Day9::parse(LARGER_EXAMPLE).unwrap();
+        let mut challenge = Challenge::new(&parsed).with_recorder();
+        challenge.execute_10_knots();
+        let recorder = challenge.recorder().unwrap();
+
+        // After `R 5`, the head has dragged five knots along
+        let (min, _) = recorder.bounds();
+        let frame = recorder.render(5);
+        let row = frame.lines().nth(-min.y as usize).unwrap();
+        assert_eq!(&row[(-min.x) as usize..][..6], "54321H");
+    }
+
+    #[test]
+    fn follows_from_any_offset() {
+        let mut knot = Position::new(0, 0, 0);
+
+        follow(&mut knot, &Position::new(1, -1, 0));
+        assert_eq!(knot, Position::new(0, 0, 0), "Touching diagonally");
+        follow(&mut knot, &Position::new(2, 2, 0));
+        assert_eq!(knot, Position::new(1, 1, 0));
+        follow(&mut knot, &Position::new(-3, 1, 0));
+        assert_eq!(knot, Position::new(0, 1, 0));
+        follow(&mut knot, &Position::new(1, 5, 0));
+        assert_eq!(knot, Position::new(1, 2, 0));
+
+        follow(&mut knot, &Position::new(2, 3, 1));
+        assert_eq!(knot, Position::new(1, 2, 0), "Touching by a corner in 3D");
+        follow(&mut knot, &Position::new(1, 1, -2));
+        assert_eq!(knot, Position::new(1, 1, -1));
+    }
+
+    #[test]
+    fn counts_the_visits_of_every_knot() {
+        let parsed = Day9::parse(LARGER_EXAMPLE).unwrap();
+        let visits = Challenge::new(&parsed).execute_generic(10);
+
+        assert_eq!(visits.len(), 10);
+        assert_eq!(visits[9], 36);
+        assert!(visits.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        let parsed = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Challenge::new(&parsed).execute_generic(2)[1], 13);
+        assert_eq!(Challenge::new(&parsed).execute_generic(1).len(), 1);
+    }
+
+    #[test]
+    fn diagonal_and_3d_moves() {
+        let moves = parse_lines::<Movement>("UR 3\nL 1").unwrap();
+        assert_eq!(Challenge::new(&moves).execute_generic(2), vec![5, 3]);
+
+        let moves = parse_lines::<Movement>("F 3\nUR 2\nB 1").unwrap();
+        assert_eq!(Challenge::new(&moves).execute_generic(2), vec![7, 4]);
+
+        let error = |s: &str| s.parse::<Movement>().err().unwrap().to_string();
+        assert_eq!(error("X 3"), "Expected R, L, U, D, F or B (found `X`)");
+        assert_eq!(
+            error("RL 3"),
+            "A move can't go twice along an axis (found `L`)"
+        );
+        assert_eq!(error(" 3"), "Expected a direction");
+    }
 }
@@ -0,0 +1,135 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+// Error for malformed puzzle inputs.
+//
+// Parsers build it from the slice of the input they couldn't understand, so the position
+// doesn't need to be threaded through them: `locate` finds the line and column of that
+// slice once the error gets back to the code holding the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    text: String,
+    // Address of the offending slice, only compared against the bounds of the input
+    address: usize,
+    day: Option<u8>,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>, text: &str) -> Self {
+        Self {
+            message: message.into(),
+            text: text.to_string(),
+            address: text.as_ptr() as usize,
+            day: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    // Sets the line and column of the offending text when it was sliced from `input`
+    pub fn locate(mut self, input: &str) -> Self {
+        if self.line.is_some() {
+            return self;
+        }
+
+        let start = input.as_ptr() as usize;
+        if self.address < start || self.address > start + input.len() {
+            return self;
+        }
+
+        let before = &input[..self.address - start];
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+
+        self.line = Some(before.matches('\n').count() + 1);
+        self.column = Some(before[line_start..].chars().count() + 1);
+        self
+    }
+
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let position = [
+            self.day.map(|day| format!("day {day}")),
+            self.line.map(|line| format!("line {line}")),
+            self.column.map(|column| format!("column {column}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if !position.is_empty() {
+            write!(f, "{}: ", position.join(", "))?;
+        }
+
+        write!(f, "{}", self.message)?;
+
+        match self.text.lines().next() {
+            Some(text) if !text.is_empty() => write!(f, " (found `{text}`)"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// Parses a number, reporting `token` as the offending text when it isn't one
+pub fn parse_number<T: FromStr>(token: &str) -> Result<T, ParseError> {
+    token
+        .trim()
+        .parse::<T>()
+        .map_err(|_| ParseError::new("Expected a number", token.trim()))
+}
+
+// Parses each line of the input with `FromStr`
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    input.lines().map(|line| line.parse::<T>()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_the_offending_text() {
+        let input = "1-2,3-4\n5-6,x-8\n";
+        let token = &input[12..13];
+
+        let err = ParseError::new("Expected a number", token)
+            .locate(input)
+            .in_day(4);
+
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.column, Some(5));
+        assert_eq!(
+            err.to_string(),
+            "day 4, line 2, column 5: Expected a number (found `x`)"
+        );
+    }
+
+    #[test]
+    fn text_outside_of_the_input_is_not_located() {
+        let input = "1\n2\n";
+        let err = parse_number::<u32>("x").unwrap_err().locate(input);
+
+        assert_eq!(err.line, None);
+        assert_eq!(err.to_string(), "Expected a number (found `x`)");
+    }
+
+    #[test]
+    fn missing_text_points_to_the_end_of_the_line() {
+        let input = "1-2,";
+        let err = ParseError::new("Missing the second section", &input[4..]).locate(input);
+
+        assert_eq!((err.line, err.column), (Some(1), Some(5)));
+    }
+}
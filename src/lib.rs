@@ -0,0 +1,33 @@
+// Solutions for the Advent of Code 2022 puzzles.
+//
+// Every day lives in its own module and implements `solution::Solution`. `runner::DAYS` is the
+// registry of all of them, which the `aoc` binary, `verify` and `bench` run.
+pub mod bench;
+pub mod cli;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod runner;
+pub mod solution;
+pub mod utils;
+pub mod verify;
@@ -1,113 +1,41 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod utils;
+use std::process;
 
-fn main() {
-    println!("Day 1");
-    day1::task_1();
-    day1::task_2();
-
-    println!("\nDay 2");
-    day2::task_1();
-    day2::task_2();
-
-    println!("\nDay 3");
-    day3::task_1();
-    day3::task_2();
-
-    println!("\nDay 4");
-    day4::task_1();
-    day4::task_2();
-
-    println!("\nDay 5");
-    day5::task_1();
-    day5::task_2();
-
-    println!("\nDay 6");
-    day6::task_1();
-    day6::task_2();
-
-    println!("\nDay 7");
-    day7::task_1();
-    day7::task_2();
-
-    println!("\nDay 8");
-    day8::task_1();
-    day8::task_2();
-
-    println!("\nDay 9");
-    day9::task_1();
-    day9::task_2();
-
-    println!("\nDay 10");
-    day10::task_1();
-    day10::task_2();
-
-    println!("\nDay 11");
-    day11::task_1();
-    day11::task_2();
+use advent_of_code_2022::{
+    bench,
+    cli::{Command, USAGE},
+    runner, verify,
+};
 
-    println!("\nDay 12");
-    day12::task_1();
-    day12::task_2();
-
-    println!("\nDay 13");
-    day13::task_1();
-    day13::task_2();
-
-    println!("\nDay 14");
-    day14::task_1();
-    day14::task_2();
-
-    println!("\nDay 15");
-    day15::task_1();
-    day15::task_2();
-
-    println!("\nDay 16");
-    day16::task_1();
-    day16::task_2();
-
-    println!("\nDay 17");
-    day17::task_1();
-    day17::task_2();
-
-    println!("\nDay 18");
-    day18::task_1();
-    day18::task_2();
-
-    println!("\nDay 19");
-    day19::task_1();
-    day19::task_2();
-
-    println!("\nDay 20");
-    day20::task_1();
-    day20::task_2();
-
-    println!("\nDay 21");
-    day21::task_1();
-    day21::task_2();
-
-    println!("\nDay 22");
-    day22::task_1();
-    day22::task_2();
+fn main() {
+    let command = match Command::from_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Run { days, options } => {
+            let failures = runner::run(&days, &options);
+            if failures > 0 {
+                eprintln!("\n{failures} part(s) failed");
+                process::exit(1);
+            }
+        }
+        Command::Verify { days, record } => match verify::verify(&days, record) {
+            Ok(0) => (),
+            Ok(failures) => {
+                eprintln!("\n{failures} part(s) failed");
+                process::exit(1);
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                process::exit(1);
+            }
+        },
+        Command::Bench { days } => process::exit(bench::run(&days)),
+        Command::List => runner::list(),
+        Command::Help => println!("{USAGE}"),
+    }
 }
@@ -0,0 +1,638 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    day1::Day1,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day2::Day2,
+    day20::Day20,
+    day21::Day21,
+    day22::Day22,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    error::ParseError,
+    solution::{Answer, Solution},
+    utils::{
+        input::{InputError, InputSource},
+        json::Json,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// The answers of the requested parts, with how long parsing and each part took
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+    pub part_times: Vec<Duration>,
+}
+
+impl Solved {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.part_times.iter().sum::<Duration>()
+    }
+}
+
+// Parses the input and solves the requested parts of a day
+pub type Solver = fn(&str, &[Part]) -> Result<Solved, ParseError>;
+
+fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Solved, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input).map_err(|err| err.locate(input))?;
+    let parse_time = start.elapsed();
+
+    let (answers, part_times) = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part1(&parsed),
+                Part::Two => S::part2(&parsed),
+            };
+            (answer, start.elapsed())
+        })
+        .unzip();
+
+    Ok(Solved {
+        parse_time,
+        answers,
+        part_times,
+    })
+}
+
+// One entry of the registry: the puzzle and the solution for it
+#[derive(Debug)]
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub solve: Solver,
+}
+
+impl Day {
+    const fn new<S: Solution>(number: u8, title: &'static str) -> Self {
+        Self {
+            number,
+            title,
+            solve: solve::<S>,
+        }
+    }
+}
+
+// Days are the same when they have the same number, the registry has one entry per day
+impl PartialEq for Day {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number
+    }
+}
+
+impl Eq for Day {}
+
+pub const DAYS: [Day; 22] = [
+    Day::new::<Day1>(1, "Calorie Counting"),
+    Day::new::<Day2>(2, "Rock Paper Scissors"),
+    Day::new::<Day3>(3, "Rucksack Reorganization"),
+    Day::new::<Day4>(4, "Camp Cleanup"),
+    Day::new::<Day5>(5, "Supply Stacks"),
+    Day::new::<Day6>(6, "Tuning Trouble"),
+    Day::new::<Day7>(7, "No Space Left On Device"),
+    Day::new::<Day8>(8, "Treetop Tree House"),
+    Day::new::<Day9>(9, "Rope Bridge"),
+    Day::new::<Day10>(10, "Cathode-Ray Tube"),
+    Day::new::<Day11>(11, "Monkey in the Middle"),
+    Day::new::<Day12>(12, "Hill Climbing Algorithm"),
+    Day::new::<Day13>(13, "Distress Signal"),
+    Day::new::<Day14>(14, "Regolith Reservoir"),
+    Day::new::<Day15>(15, "Beacon Exclusion Zone"),
+    Day::new::<Day16>(16, "Proboscidea Volcanium"),
+    Day::new::<Day17>(17, "Pyroclastic Flow"),
+    Day::new::<Day18>(18, "Boiling Boulders"),
+    Day::new::<Day19>(19, "Not Enough Minerals"),
+    Day::new::<Day20>(20, "Grove Positioning System"),
+    Day::new::<Day21>(21, "Monkey Math"),
+    Day::new::<Day22>(22, "Monkey Map"),
+];
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+// Why a day couldn't produce its answers
+#[derive(Debug)]
+pub enum DayError {
+    NoInput(InputError),
+    InvalidInput(ParseError),
+    // With the message the solution panicked with
+    Panicked(String),
+}
+
+impl Display for DayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayError::NoInput(err) => write!(f, "No input: {err}"),
+            DayError::InvalidInput(err) => write!(f, "Invalid input: {err}"),
+            DayError::Panicked(message) => write!(f, "The solution panicked: {message}"),
+        }
+    }
+}
+
+// Reads the input of a day and solves the requested parts, turning panics into errors
+pub fn solve_day(day: &Day, parts: &[Part], source: &InputSource) -> Result<Solved, DayError> {
+    let input = source.read(day.number).map_err(DayError::NoInput)?;
+    solve_input(day, &input, parts)
+}
+
+thread_local! {
+    // Whether the thread is solving a day, whose panics are reported with its results
+    static SOLVING: Cell<bool> = const { Cell::new(false) };
+}
+
+// Solves the requested parts of a day from its input, turning panics into errors
+pub fn solve_input(day: &Day, input: &str, parts: &[Part]) -> Result<Solved, DayError> {
+    // The default hook would print the panic on stderr, in the middle of the other days
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SOLVING.with(Cell::get) {
+                default_hook(info);
+            }
+        }));
+    });
+
+    SOLVING.with(|solving| solving.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| (day.solve)(input, parts)));
+    SOLVING.with(|solving| solving.set(false));
+
+    match result {
+        Ok(Ok(solved)) => Ok(solved),
+        Ok(Err(err)) => Err(DayError::InvalidInput(err.in_day(day.number))),
+        Err(payload) => Err(DayError::Panicked(panic_message(payload))),
+    }
+}
+
+// The message of a panic, `panic!` gives a `&str` or a `String`
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => String::from("Unknown panic"),
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+// How `run` solves and prints the days
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions {
+    pub parts: Vec<Part>,
+    pub input: InputSource,
+    // Print how long parsing and each part took, JSON always includes it
+    pub time: bool,
+    pub format: Format,
+    // Threads solving parts at the same time
+    pub jobs: usize,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            parts: Part::ALL.to_vec(),
+            input: InputSource::Default,
+            time: false,
+            format: Format::Text,
+            jobs: 1,
+        }
+    }
+}
+
+// Solves the parts of the days on `jobs` threads, reading their inputs with `read`, and calls
+// `on_day` with the results of each day, in day order, as soon as the day and the ones before
+// it are done.
+fn solve_days<R, F>(days: &[&Day], parts: &[Part], read: R, jobs: usize, mut on_day: F)
+where
+    R: Fn(u8) -> Result<String, InputError>,
+    F: FnMut(usize, Result<Solved, DayError>),
+{
+    if jobs <= 1 {
+        for (idx, day) in days.iter().enumerate() {
+            let result = read(day.number)
+                .map_err(DayError::NoInput)
+                .and_then(|input| solve_input(day, &input, parts));
+            on_day(idx, result);
+        }
+        return;
+    }
+
+    // Read up front, stdin can't be read once per part
+    let (inputs, mut input_errors): (Vec<_>, Vec<_>) = days
+        .iter()
+        .map(|day| match read(day.number) {
+            Ok(input) => (Some(input), None),
+            Err(err) => (None, Some(err)),
+        })
+        .unzip();
+
+    // Every part is solved on its own, parsing the input again
+    let tasks = inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, input)| input.is_some())
+        .flat_map(|(day_idx, _)| (0..parts.len()).map(move |part_idx| (day_idx, part_idx)))
+        .collect::<Vec<_>>();
+
+    let mut part_results = days
+        .iter()
+        .map(|_| parts.iter().map(|_| None).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let next_task = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(tasks.len()) {
+            let (sender, tasks, next_task, inputs) = (sender.clone(), &tasks, &next_task, &inputs);
+
+            scope.spawn(move || {
+                while let Some(&(day_idx, part_idx)) =
+                    tasks.get(next_task.fetch_add(1, Ordering::Relaxed))
+                {
+                    let input = inputs[day_idx].as_deref().unwrap_or_default();
+                    let result = solve_input(days[day_idx], input, &parts[part_idx..=part_idx]);
+
+                    if sender.send((day_idx, part_idx, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut next_day = 0;
+        let mut received = receiver.iter();
+
+        loop {
+            while next_day < days.len() {
+                if let Some(err) = input_errors[next_day].take() {
+                    on_day(next_day, Err(DayError::NoInput(err)));
+                } else if part_results[next_day].iter().all(Option::is_some) {
+                    let results = part_results[next_day].iter_mut().flat_map(Option::take);
+                    on_day(next_day, merge(results));
+                } else {
+                    break;
+                }
+                next_day += 1;
+            }
+
+            match received.next() {
+                Some((day_idx, part_idx, result)) => part_results[day_idx][part_idx] = Some(result),
+                None => break,
+            }
+        }
+    });
+}
+
+// Joins the results of the parts of a day, solved on their own. The day fails when any part
+// fails, like when they are solved together.
+fn merge(results: impl Iterator<Item = Result<Solved, DayError>>) -> Result<Solved, DayError> {
+    let mut merged = Solved {
+        parse_time: Duration::ZERO,
+        answers: vec![],
+        part_times: vec![],
+    };
+
+    for result in results {
+        let solved = result?;
+        // Every part parsed the input, keep the slowest
+        merged.parse_time = merged.parse_time.max(solved.parse_time);
+        merged.answers.extend(solved.answers);
+        merged.part_times.extend(solved.part_times);
+    }
+
+    Ok(merged)
+}
+
+// Runs the selected parts of the selected days, in day order, and prints the answers.
+// Returns the number of parts that failed.
+pub fn run(days: &[&Day], options: &RunOptions) -> usize {
+    let parts = &options.parts;
+    let mut failures = 0;
+    let mut total_time = Duration::ZERO;
+
+    if options.format == Format::Json {
+        println!("[");
+    }
+
+    let read = |day| options.input.read(day);
+    solve_days(days, parts, read, options.jobs, |idx, result| {
+        let day = days[idx];
+
+        match &result {
+            Ok(solved) => total_time += solved.total_time(),
+            Err(_) => failures += parts.len(),
+        }
+
+        match options.format {
+            Format::Text => print_text(idx, day, parts, &result, options.time),
+            Format::Json => {
+                let last_day = idx + 1 == days.len();
+                for (part_idx, record) in json_records(day, parts, &result).iter().enumerate() {
+                    let separator = match last_day && part_idx + 1 == parts.len() {
+                        true => "",
+                        false => ",",
+                    };
+                    println!("  {record}{separator}");
+                }
+            }
+        }
+    });
+
+    match options.format {
+        Format::Text if options.time && days.len() > 1 => println!("\nTotal: {total_time:.2?}"),
+        Format::Text => (),
+        Format::Json => println!("]"),
+    }
+
+    failures
+}
+
+fn print_text(
+    idx: usize,
+    day: &Day,
+    parts: &[Part],
+    result: &Result<Solved, DayError>,
+    time: bool,
+) {
+    if idx != 0 {
+        println!();
+    }
+    println!("Day {}", day.number);
+
+    match result {
+        Ok(solved) => {
+            if time {
+                println!("Parse: {:.2?}", solved.parse_time);
+            }
+            for ((part, answer), elapsed) in
+                parts.iter().zip(&solved.answers).zip(&solved.part_times)
+            {
+                print_answer(*part, answer, time.then_some(*elapsed));
+            }
+        }
+        Err(err) => eprintln!("{err}"),
+    }
+}
+
+fn print_answer(part: Part, answer: &Answer, elapsed: Option<Duration>) {
+    let elapsed = elapsed
+        .map(|elapsed| format!(" ({elapsed:.2?})"))
+        .unwrap_or_default();
+
+    match answer {
+        Answer::Grid(_) => println!("Part {part}:{elapsed}\n{answer}"),
+        _ => println!("Part {part}: {answer}{elapsed}"),
+    }
+}
+
+// One object per part, with its answer, how long it took and whether it worked:
+//
+//     {"day":1,"part":1,"status":"ok","answer":69501,"answer_type":"int",
+//      "parse_time_ns":52000,"solve_time_ns":800,"error":null}
+//
+// Answers are null when the part is unimplemented or the day failed, and so are the times when
+// the day failed.
+fn json_records(day: &Day, parts: &[Part], result: &Result<Solved, DayError>) -> Vec<Json> {
+    parts
+        .iter()
+        .enumerate()
+        .map(|(idx, part)| {
+            let (status, answer, answer_type, parse_time, solve_time, error) = match result {
+                Ok(solved) => {
+                    let (status, answer, answer_type) = match &solved.answers[idx] {
+                        Answer::Int(value) => ("ok", Json::from(*value), Json::from("int")),
+                        Answer::Text(value) => {
+                            ("ok", Json::from(value.as_str()), Json::from("text"))
+                        }
+                        grid @ Answer::Grid(_) => {
+                            ("ok", Json::from(grid.to_string()), Json::from("grid"))
+                        }
+                        Answer::Unimplemented => ("unimplemented", Json::Null, Json::Null),
+                    };
+                    let nanos = |duration: Duration| Json::Number(duration.as_nanos() as i64);
+
+                    (
+                        status,
+                        answer,
+                        answer_type,
+                        nanos(solved.parse_time),
+                        nanos(solved.part_times[idx]),
+                        Json::Null,
+                    )
+                }
+                Err(err) => (
+                    "error",
+                    Json::Null,
+                    Json::Null,
+                    Json::Null,
+                    Json::Null,
+                    Json::from(err.to_string()),
+                ),
+            };
+
+            Json::object([
+                ("day", Json::Number(day.number.into())),
+                ("part", Json::Number(part.number().into())),
+                ("status", Json::from(status)),
+                ("answer", answer),
+                ("answer_type", answer_type),
+                ("parse_time_ns", parse_time),
+                ("solve_time_ns", solve_time),
+                ("error", error),
+            ])
+        })
+        .collect()
+}
+
+pub fn list() {
+    for day in DAYS.iter() {
+        println!("{:>2}  {}", day.number, day.title);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::parse_number;
+
+    // Answers the sum and the largest of the numbers of the input
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Parsed = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            input.lines().map(parse_number).collect()
+        }
+
+        fn part1(numbers: &Self::Parsed) -> Answer {
+            numbers.iter().sum::<i64>().into()
+        }
+
+        fn part2(numbers: &Self::Parsed) -> Answer {
+            numbers.iter().max().copied().unwrap_or_default().into()
+        }
+    }
+
+    // Solves part 1 like `Numbers`, but panics in part 2
+    struct Panics;
+
+    impl Solution for Panics {
+        type Parsed = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            Numbers::parse(input)
+        }
+
+        fn part1(numbers: &Self::Parsed) -> Answer {
+            Numbers::part1(numbers)
+        }
+
+        fn part2(_: &Self::Parsed) -> Answer {
+            panic!("Part 2 is broken")
+        }
+    }
+
+    const TEST_DAYS: [Day; 5] = [
+        Day::new::<Numbers>(1, "Ok"),
+        Day::new::<Numbers>(2, "Missing input"),
+        Day::new::<Panics>(3, "Panics"),
+        Day::new::<Numbers>(4, "Invalid input"),
+        Day::new::<Numbers>(5, "Ok"),
+    ];
+
+    // The inputs of the test days: day 2 has none and day 4 has a word instead of a number
+    fn read(day: u8) -> Result<String, InputError> {
+        match day {
+            2 => Err(InputError::NotFound { tried: vec![] }),
+            4 => Ok(String::from("1\ntwo\n")),
+            day => Ok(format!("{day}\n{}\n", day * 10)),
+        }
+    }
+
+    // The days passed to `on_day`, with their answers or their error
+    fn solve_all(jobs: usize) -> Vec<(usize, Result<Vec<Answer>, String>)> {
+        let days = TEST_DAYS.iter().collect::<Vec<_>>();
+        let mut calls = vec![];
+
+        solve_days(&days, &Part::ALL, read, jobs, |idx, result| {
+            let result = result.map(|solved| {
+                assert_eq!(solved.part_times.len(), solved.answers.len());
+                solved.answers
+            });
+            calls.push((idx, result.map_err(|err| err.to_string())));
+        });
+
+        calls
+    }
+
+    #[test]
+    fn days_are_reported_in_order_with_any_number_of_jobs() {
+        let expected = vec![
+            (0, Ok(vec![Answer::Int(11), Answer::Int(10)])),
+            (
+                1,
+                Err(String::from("No input: ") + &read(2).unwrap_err().to_string()),
+            ),
+            (
+                2,
+                Err(String::from("The solution panicked: Part 2 is broken")),
+            ),
+            (
+                3,
+                Err(String::from(
+                    "Invalid input: day 4, line 2, column 1: Expected a number (found `two`)",
+                )),
+            ),
+            (4, Ok(vec![Answer::Int(55), Answer::Int(50)])),
+        ];
+
+        assert_eq!(solve_all(1), expected);
+        for jobs in [2, 3, 8] {
+            assert_eq!(solve_all(jobs), expected, "With {jobs} jobs");
+        }
+    }
+
+    #[test]
+    fn merging_keeps_the_slowest_parse_and_the_first_error() {
+        let solved = |parse_ms, answer: i64| {
+            Ok(Solved {
+                parse_time: Duration::from_millis(parse_ms),
+                answers: vec![Answer::Int(answer)],
+                part_times: vec![Duration::from_millis(answer as u64)],
+            })
+        };
+
+        let merged = merge([solved(3, 1), solved(5, 2)].into_iter()).unwrap();
+        assert_eq!(merged.parse_time, Duration::from_millis(5));
+        assert_eq!(merged.answers, vec![Answer::Int(1), Answer::Int(2)]);
+        assert_eq!(
+            merged.part_times,
+            vec![Duration::from_millis(1), Duration::from_millis(2)]
+        );
+
+        let panicked = || Err(DayError::Panicked(String::from("Oops")));
+        let failed = merge([solved(3, 1), panicked(), solved(5, 2)].into_iter());
+        assert!(matches!(failed, Err(DayError::Panicked(message)) if message == "Oops"));
+    }
+}
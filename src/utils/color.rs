@@ -0,0 +1,43 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use crate::utils::image::Rgb;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+        }
+    }
+}
+
+// Colours are only used when stdout is a terminal and $NO_COLOR isn't set
+pub fn enabled() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+// Wraps `text` in the escape codes of `color`. Pad the text before painting it, the escape
+// codes would count towards the width otherwise.
+pub fn paint(text: &str, color: Color) -> String {
+    match enabled() {
+        true => format!("\x1b[{}m{text}\x1b[0m", color.code()),
+        false => text.to_string(),
+    }
+}
+
+// Paints the background of `text` with any colour. Unlike `paint` it always adds the escape
+// codes, for renderers that were asked for colours.
+pub fn on_rgb(text: &str, [r, g, b]: Rgb) -> String {
+    format!("\x1b[48;2;{r};{g};{b}m{text}\x1b[0m")
+}
@@ -0,0 +1,314 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+// Numbers usable as coordinates
+pub trait Coord:
+    Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    // The absolute difference, as the same type
+    fn distance(self, other: Self) -> Self;
+    // -1, 0 or 1, never -1 for unsigned types
+    fn signum(self) -> Self;
+}
+
+macro_rules! signed_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            fn distance(self, other: Self) -> Self {
+                (self - other).abs()
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+macro_rules! unsigned_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            fn distance(self, other: Self) -> Self {
+                self.abs_diff(other)
+            }
+
+            fn signum(self) -> Self {
+                (self > 0) as $t
+            }
+        }
+    )*};
+}
+
+signed_coord!(i32, i64, isize);
+unsigned_coord!(u32, u64, usize);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// Offsets of the neighbours sharing a side, clockwise from the one above
+pub const ORTHOGONAL: [Vec2; 4] = [
+    Vec2::new(0, -1),
+    Vec2::new(1, 0),
+    Vec2::new(0, 1),
+    Vec2::new(-1, 0),
+];
+
+// Offsets of the neighbours sharing a side or a corner, clockwise from the one above
+pub const ADJACENT: [Vec2; 8] = [
+    Vec2::new(0, -1),
+    Vec2::new(1, -1),
+    Vec2::new(1, 0),
+    Vec2::new(1, 1),
+    Vec2::new(0, 1),
+    Vec2::new(-1, 1),
+    Vec2::new(-1, 0),
+    Vec2::new(-1, -1),
+];
+
+// Offsets of the neighbours sharing a face in 3D
+pub const FACES: [Vec3; 6] = [
+    Vec3::new(-1, 0, 0),
+    Vec3::new(1, 0, 0),
+    Vec3::new(0, -1, 0),
+    Vec3::new(0, 1, 0),
+    Vec3::new(0, 0, -1),
+    Vec3::new(0, 0, 1),
+];
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Coord> Vec2<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl Vec2 {
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        ORTHOGONAL.into_iter().map(move |offset| self + offset)
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        ADJACENT.into_iter().map(move |offset| self + offset)
+    }
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Coord> Vec3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
+    }
+
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+impl Vec3 {
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        FACES.into_iter().map(move |offset| self + offset)
+    }
+}
+
+// The arithmetic is the same for both, component by component
+macro_rules! vector_ops {
+    ($name:ident { $($field:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                Self { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $($field: -self.$field),* }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+vector_ops!(Vec2 { x, y });
+vector_ops!(Vec3 { x, y, z });
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(value: Vec2<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Vec3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+// The four directions on a grid where y grows downwards, as in `utils::grid`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // Clockwise from up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector_arithmetic() {
+        let a = Vec2::new(3, -4);
+        let b = Vec2::new(-1, 2);
+
+        assert_eq!(a + b, Vec2::new(2, -2));
+        assert_eq!(a - b, Vec2::new(4, -6));
+        assert_eq!(a * 2, Vec2::new(6, -8));
+        assert_eq!(-a, Vec2::new(-3, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!((a - b).signum(), Vec2::new(1, -1));
+
+        let c = Vec3::new(1, 2, 3);
+        assert_eq!(c - Vec3::new(1, 0, 5), Vec3::new(0, 2, -2));
+        assert_eq!(c.manhattan(Vec3::default()), 6);
+        assert_eq!(c.neighbors6().count(), 6);
+
+        assert_eq!(Vec2::new(2_usize, 7).manhattan(Vec2::new(5, 1)), 9);
+        assert_eq!(Vec2::new(0_usize, 7).signum(), Vec2::new(0, 1));
+    }
+
+    #[test]
+    fn directions_rotate() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(
+            Direction::ALL.map(|dir| dir.offset()),
+            ORTHOGONAL,
+            "Offsets follow the same order as the neighbours"
+        );
+        assert_eq!(Vec2::new(0, 0).neighbors8().count(), 8);
+    }
+}
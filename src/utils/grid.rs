@@ -0,0 +1,348 @@
+use std::{
+    fmt::Display,
+    iter,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    error::ParseError,
+    utils::geom::{Vec2, ADJACENT, ORTHOGONAL},
+};
+
+// A position in a grid, as (x, y) with y growing downwards
+pub type Pos = (usize, usize);
+
+// A rectangular grid stored row by row in a single Vec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // Panics when the rows don't have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map(Vec::len).unwrap_or_default();
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "All rows must have the same length"
+        );
+
+        Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    // Parses a grid with a row per line, mapping each char with `f`, which returns the error
+    // message for chars it doesn't expect. Rows must have the same length.
+    pub fn parse<F>(s: &str, f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Result<T, &'static str>,
+    {
+        let rows = parse_rows(s, f)?;
+        let width = rows.first().map(|(_, row)| row.len()).unwrap_or_default();
+
+        if let Some((line, _)) = rows.iter().find(|(_, row)| row.len() != width) {
+            return Err(ParseError::new("All rows must have the same length", line));
+        }
+
+        Self::from_parsed(s, width, rows)
+    }
+
+    // Like `parse`, but rows shorter than the longest one are padded with `fill`
+    pub fn parse_padded<F>(s: &str, fill: T, f: F) -> Result<Self, ParseError>
+    where
+        T: Clone,
+        F: FnMut(char) -> Result<T, &'static str>,
+    {
+        let mut rows = parse_rows(s, f)?;
+        let width = rows
+            .iter()
+            .map(|(_, row)| row.len())
+            .max()
+            .unwrap_or_default();
+
+        for (_, row) in rows.iter_mut() {
+            row.resize(width, fill.clone());
+        }
+
+        Self::from_parsed(s, width, rows)
+    }
+
+    fn from_parsed(s: &str, width: usize, rows: Vec<(&str, Vec<T>)>) -> Result<Self, ParseError> {
+        if width == 0 {
+            return Err(ParseError::new("The grid is empty", s));
+        }
+
+        Ok(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flat_map(|(_, row)| row).collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        match self.contains(pos) {
+            true => Some(&mut self.cells[pos.1 * self.width + pos.0]),
+            false => None,
+        }
+    }
+
+    // The position `offset` away from `pos`, if it's inside the grid
+    pub fn step(&self, (x, y): Pos, offset: Vec2) -> Option<Pos> {
+        let pos = (
+            x.checked_add_signed(offset.x as isize)?,
+            y.checked_add_signed(offset.y as isize)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |offset| self.step(pos, *offset))
+    }
+
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ADJACENT
+            .iter()
+            .filter_map(move |offset| self.step(pos, *offset))
+    }
+
+    // The positions from `pos` towards `offset` until the edge, without `pos` itself
+    pub fn ray(&self, pos: Pos, offset: Vec2) -> impl Iterator<Item = Pos> + '_ {
+        iter::successors(self.step(pos, offset), move |pos| self.step(*pos, offset))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + Clone {
+        // Chunks can't be empty, a grid without columns has no cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(
+        &self,
+        x: usize,
+    ) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + Clone {
+        assert!(x < self.width, "Column {x} is outside of the grid");
+        (0..self.height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    // Adds a row after the last one. Panics when its length isn't the width of the grid.
+    pub fn push_row(&mut self, row: Vec<T>) {
+        assert_eq!(row.len(), self.width, "Rows must be as wide as the grid");
+        self.cells.extend(row);
+        self.height += 1;
+    }
+
+    pub fn pop_row(&mut self) -> Option<Vec<T>> {
+        if self.height == 0 {
+            return None;
+        }
+
+        self.height -= 1;
+        Some(self.cells.split_off(self.height * self.width))
+    }
+
+    // The cells, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    // The cells with their positions, row by row
+    pub fn iter_positions(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx % width, idx / width), cell))
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter_positions()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Pos, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .iter_positions()
+                .map(|(pos, cell)| f(pos, cell))
+                .collect(),
+        }
+    }
+}
+
+fn parse_rows<T, F>(s: &str, mut f: F) -> Result<Vec<(&str, Vec<T>)>, ParseError>
+where
+    F: FnMut(char) -> Result<T, &'static str>,
+{
+    s.lines()
+        .map(|line| {
+            let row = line
+                .char_indices()
+                .map(|(offset, c)| {
+                    f(c).map_err(|message| {
+                        ParseError::new(message, &line[offset..offset + c.len_utf8()])
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok((line, row))
+        })
+        .collect()
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "{pos:?} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside of the {width}x{height} grid"),
+        }
+    }
+}
+
+// Writes the cells of each row next to each other, with a line per row
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y != 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(s, |c| c.to_digit(10).ok_or("Expected a digit"))
+    }
+
+    #[test]
+    fn parses_and_writes_grids() {
+        let grid = digits("123\n456\n").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        assert!(digits("12\n3").is_err());
+        assert!(digits("12\n3a").is_err());
+        assert!(digits("").is_err());
+
+        let padded = Grid::parse_padded("1\n234", 0, |c| c.to_digit(10).ok_or("Expected a digit"));
+        assert_eq!(padded.unwrap().to_string(), "100\n234");
+    }
+
+    #[test]
+    fn walks_neighbours_and_rays() {
+        let grid = Grid::new(3, 3, ());
+
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+        assert_eq!(
+            grid.ray((2, 1), Vec2::new(-1, 0)).collect::<Vec<_>>(),
+            vec![(1, 1), (0, 1)]
+        );
+        assert_eq!(grid.ray((0, 0), Vec2::new(0, -1)).count(), 0);
+    }
+
+    #[test]
+    fn grows_and_shrinks_by_rows() {
+        let mut grid = Grid::new(2, 0, false);
+
+        grid.push_row(vec![true, false]);
+        grid.push_row(vec![false, false]);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.position(|cell| *cell), Some((0, 0)));
+
+        assert_eq!(grid.pop_row(), Some(vec![false, false]));
+        assert_eq!(grid, Grid::from_rows(vec![vec![true, false]]));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::utils::grid::Grid;
+
+// A colour as its red, green and blue parts
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const RED: Rgb = [255, 0, 0];
+
+pub fn gray(value: u8) -> Rgb {
+    [value; 3]
+}
+
+// Writes `pixels` as a plain PGM image, each row starting a new line of text
+pub fn pgm(pixels: &Grid<u8>) -> String {
+    image("P2", pixels, std::slice::from_ref)
+}
+
+// Writes `pixels` as a plain PPM image, each row starting a new line of text
+pub fn ppm(pixels: &Grid<Rgb>) -> String {
+    image("P3", pixels, |rgb| rgb.as_slice())
+}
+
+// Plain images can't have lines longer than that
+const LINE_WIDTH: usize = 70;
+
+fn image<T>(magic: &str, pixels: &Grid<T>, samples: impl Fn(&T) -> &[u8]) -> String {
+    let mut image = format!("{magic}\n{} {}\n255\n", pixels.width(), pixels.height());
+
+    for row in pixels.rows() {
+        let mut line = String::new();
+
+        for sample in row.iter().flat_map(&samples) {
+            let sample = sample.to_string();
+            if !line.is_empty() && line.len() + 1 + sample.len() > LINE_WIDTH {
+                image.push_str(&line);
+                image.push('\n');
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&sample);
+        }
+
+        image.push_str(&line);
+        image.push('\n');
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_plain_images() {
+        let pixels = Grid::from_rows(vec![vec![0, 128], vec![255, 7]]);
+
+        assert_eq!(pgm(&pixels), "P2\n2 2\n255\n0 128\n255 7\n");
+        assert_eq!(
+            ppm(&pixels.map(|_, value| gray(*value))),
+            "P3\n2 2\n255\n0 0 0 128 128 128\n255 255 255 7 7 7\n"
+        );
+    }
+
+    #[test]
+    fn wraps_long_rows() {
+        let image = ppm(&Grid::new(10, 2, [255; 3]));
+        let lines = image.lines().skip(3).collect::<Vec<_>>();
+
+        // 17 samples of 3 digits and their spaces fit in 70 columns, 18 don't
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| line.len() <= LINE_WIDTH));
+        assert_eq!(lines[0].split(' ').count(), 17);
+        assert_eq!(lines[1].split(' ').count(), 13);
+    }
+}
@@ -0,0 +1,143 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+// Directory with the puzzle inputs, used instead of the default lookup when set
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+pub const CRATE_ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    // Look for `day_N.txt` in $AOC_INPUT_DIR, or in `inputs/` relative to the working
+    // directory and then to the crate root
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+impl From<&str> for InputSource {
+    fn from(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound {
+        tried: Vec<PathBuf>,
+    },
+    Io {
+        path: Option<PathBuf>,
+        error: io::Error,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound { tried } => {
+                let tried = tried
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "Input file not found (tried: {tried})")
+            }
+            InputError::Io {
+                path: Some(path),
+                error,
+            } => write!(f, "Can't read {}: {error}", path.display()),
+            InputError::Io { path: None, error } => write!(f, "Can't read stdin: {error}"),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl InputSource {
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        match self {
+            InputSource::Default => {
+                let tried = Self::candidates(day);
+                match tried.iter().find(|path| path.is_file()) {
+                    Some(path) => read_file(path),
+                    None => Err(InputError::NotFound { tried }),
+                }
+            }
+            InputSource::File(path) => match path.is_file() {
+                true => read_file(path),
+                false => Err(InputError::NotFound {
+                    tried: vec![path.clone()],
+                }),
+            },
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|error| InputError::Io { path: None, error })?;
+                Ok(contents)
+            }
+        }
+    }
+
+    // Paths where the input of a day is looked for, in order
+    fn candidates(day: u8) -> Vec<PathBuf> {
+        let filename = format!("day_{day}.txt");
+
+        if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+            return vec![Path::new(&dir).join(filename)];
+        }
+
+        let relative = Path::new("inputs").join(&filename);
+        let from_root = Path::new(CRATE_ROOT).join("inputs").join(&filename);
+
+        match env::current_dir() {
+            Ok(cwd) if cwd == Path::new(CRATE_ROOT) => vec![relative],
+            _ => vec![relative, from_root],
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|error| InputError::Io {
+        path: Some(path.to_path_buf()),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_from_arg() {
+        assert_eq!(InputSource::from("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from("example.txt"),
+            InputSource::File(PathBuf::from("example.txt"))
+        );
+    }
+
+    #[test]
+    fn default_finds_the_crate_inputs() {
+        assert!(InputSource::Default.read(1).is_ok());
+    }
+
+    #[test]
+    fn missing_file_names_the_path() {
+        let err = InputSource::from("does/not/exist.txt").read(1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Input file not found (tried: does/not/exist.txt)"
+        );
+    }
+}
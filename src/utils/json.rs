@@ -0,0 +1,286 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    error::ParseError,
+    utils::parser::{PResult, Parser},
+};
+
+// A JSON value, written compactly by `Display` and read back by `FromStr`. Objects keep the
+// order of their keys. Only integers are supported as numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Json)>) -> Self {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    // The value of `key`, when this is an object that has it
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Json::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Self {
+        Json::Number(value)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Json::Null)
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+
+    write!(f, "\"")
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Number(value) => write!(f, "{value}"),
+            Json::String(value) => write_string(f, value),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl FromStr for Json {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::parse_all(s, parse_value)
+    }
+}
+
+fn parse_value(p: &mut Parser) -> PResult<Json> {
+    p.whitespace();
+
+    match p.rest().chars().next() {
+        Some('n') => p.literal("null").map(|_| Json::Null),
+        Some('t' | 'f') => p
+            .one_of(&[("true", true), ("false", false)])
+            .map(Json::Bool),
+        Some('-' | '0'..='9') => p.signed().map(Json::Number),
+        Some('"') => parse_string(p).map(Json::String),
+        Some('[') => {
+            p.literal("[")?;
+            if p.eat("]") {
+                return Ok(Json::Array(vec![]));
+            }
+
+            let values = p.list(",", parse_value)?;
+            p.literal("]")?;
+            Ok(Json::Array(values))
+        }
+        Some('{') => {
+            p.literal("{")?;
+            if p.eat("}") {
+                return Ok(Json::Object(vec![]));
+            }
+
+            let fields = p.list(",", |p| {
+                let key = parse_string(p)?;
+                p.literal(":")?;
+                Ok((key, parse_value(p)?))
+            })?;
+            p.literal("}")?;
+            Ok(Json::Object(fields))
+        }
+        _ => Err(p.error("Expected a JSON value")),
+    }
+}
+
+fn parse_string(p: &mut Parser) -> PResult<String> {
+    p.literal("\"")?;
+
+    let text = p.rest();
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+
+    while let Some((idx, c)) = chars.next() {
+        let c = match c {
+            '"' => {
+                p.advance(idx + 1);
+                return Ok(value);
+            }
+            '\\' => match chars.next().map(|(_, escaped)| escaped) {
+                Some('"') => '"',
+                Some('\\') => '\\',
+                Some('/') => '/',
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('b') => '\u{8}',
+                Some('f') => '\u{c}',
+                Some('u') => {
+                    let code = text
+                        .get(idx + 2..idx + 6)
+                        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| ParseError::new("Invalid escape", &text[idx..]))?;
+                    chars.nth(3);
+                    code
+                }
+                _ => return Err(ParseError::new("Invalid escape", &text[idx..])),
+            },
+            c => c,
+        };
+
+        value.push(c);
+    }
+
+    Err(p.error("Unterminated string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_compact_json() {
+        let value = Json::object([
+            ("day", Json::Number(10)),
+            ("answer", Json::from("##..\n\"#\"")),
+            ("error", Json::from(None::<String>)),
+            (
+                "parts",
+                Json::Array(vec![Json::from(true), Json::Number(-1)]),
+            ),
+        ]);
+
+        assert_eq!(
+            value.to_string(),
+            r###"{"day":10,"answer":"##..\n\"#\"","error":null,"parts":[true,-1]}"###
+        );
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let value = Json::object([
+            ("name", Json::from("a \"b\"\n\\ é")),
+            ("size", Json::Number(-42)),
+            ("empty", Json::Array(vec![])),
+            ("nested", Json::object([("ok", Json::from(false))])),
+            ("nothing", Json::Null),
+        ]);
+
+        assert_eq!(value.to_string().parse::<Json>(), Ok(value.clone()));
+        assert_eq!(
+            " { \"a\" : [ 1 , \"\\u00e9\\t\" ] , \"b\" : { } } ".parse::<Json>(),
+            Ok(Json::object([
+                ("a", Json::Array(vec![Json::Number(1), Json::from("é\t")])),
+                ("b", Json::Object(vec![])),
+            ]))
+        );
+        assert_eq!(value.get("size").and_then(Json::as_i64), Some(-42));
+        assert_eq!(value.get("missing"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        let error = |s: &str| s.parse::<Json>().unwrap_err().to_string();
+
+        assert_eq!(error("[1,]"), "Expected a JSON value (found `]`)");
+        assert_eq!(error("{\"a\" 1}"), "Expected `:` (found `1}`)");
+        assert_eq!(error("\"abc"), "Unterminated string (found `abc`)");
+        assert_eq!(error("\"\\x\""), "Invalid escape (found `\\x\"`)");
+        assert_eq!(error("[1] 2"), "Unexpected text (found `2`)");
+        assert_eq!(error("1.5"), "Unexpected text (found `.5`)");
+    }
+}
@@ -1,9 +1,8 @@
-use std::{fs::File, io::Read};
-
-pub fn get_input_content(path: &str) -> String {
-    let mut file = File::open(path).expect("File not found");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .expect("Something went wrong reading the file");
-    contents
-}
+pub mod color;
+pub mod geom;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod json;
+pub mod parser;
+pub mod search;
@@ -0,0 +1,256 @@
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+// A cursor over the text being parsed, with small parsers that can be combined by calling
+// them in sequence or passing them to `list` and `field`.
+//
+// Tokens can be separated by any whitespace: every parser skips it before matching, so
+// literals shouldn't start with it. Errors point at the text that didn't match, which keeps
+// `ParseError::locate` working when the text was sliced from the input.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    rest: &'a str,
+}
+
+pub type PResult<T> = Result<T, ParseError>;
+
+impl<'a> Parser<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Self { rest: s }
+    }
+
+    // Parses the whole of `s` with `f`, failing when something is left after it
+    pub fn parse_all<T>(s: &'a str, f: impl FnOnce(&mut Self) -> PResult<T>) -> PResult<T> {
+        let mut parser = Self::new(s);
+        let value = f(&mut parser)?;
+        parser.end()?;
+
+        Ok(value)
+    }
+
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    // An error pointing at the text that's left
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(message, self.rest)
+    }
+
+    pub fn whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    pub fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        self.whitespace();
+
+        let end = self.rest.find(|c| !f(c)).unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(end);
+        self.rest = rest;
+
+        taken
+    }
+
+    // Consumes the next `len` bytes as they are, without skipping whitespace
+    pub fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+
+        taken
+    }
+
+    // Consumes `literal` if the text starts with it
+    pub fn eat(&mut self, literal: &str) -> bool {
+        self.whitespace();
+
+        match self.rest.strip_prefix(literal) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn literal(&mut self, literal: &str) -> PResult<()> {
+        match self.eat(literal) {
+            true => Ok(()),
+            false => Err(self.error(format!("Expected `{literal}`"))),
+        }
+    }
+
+    // The value paired with the first of `options` the text starts with
+    pub fn one_of<T: Copy>(&mut self, options: &[(&str, T)]) -> PResult<T> {
+        if let Some((_, value)) = options.iter().find(|(literal, _)| self.eat(literal)) {
+            return Ok(*value);
+        }
+
+        let expected = options
+            .iter()
+            .map(|(literal, _)| format!("`{literal}`"))
+            .collect::<Vec<_>>();
+
+        Err(self.error(format!("Expected one of {}", expected.join(", "))))
+    }
+
+    // A run of letters and digits
+    pub fn word(&mut self) -> PResult<&'a str> {
+        match self.take_while(|c| c.is_alphanumeric()) {
+            "" => Err(self.error("Expected a word")),
+            word => Ok(word),
+        }
+    }
+
+    pub fn unsigned<T: FromStr>(&mut self) -> PResult<T> {
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        number(digits, self)
+    }
+
+    // A number with an optional `-` or `+` sign
+    pub fn signed<T: FromStr>(&mut self) -> PResult<T> {
+        self.whitespace();
+
+        let sign = match self.rest.starts_with(['-', '+']) {
+            true => 1,
+            false => 0,
+        };
+        let end = self.rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(self.rest.len(), |end| end + sign);
+
+        let (digits, rest) = self.rest.split_at(end);
+        if digits.len() == sign {
+            return Err(self.error("Expected a number"));
+        }
+
+        self.rest = rest;
+        number(digits, self)
+    }
+
+    // `label` followed by a value, like `Test: divisible by 23`
+    pub fn field<T>(
+        &mut self,
+        label: &str,
+        value: impl FnOnce(&mut Self) -> PResult<T>,
+    ) -> PResult<T> {
+        self.literal(label)?;
+        value(self)
+    }
+
+    // One or more items separated by `separator`
+    pub fn list<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> PResult<T>,
+    ) -> PResult<Vec<T>> {
+        let mut items = vec![item(self)?];
+
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+
+        Ok(items)
+    }
+
+    // Fails when there's anything but whitespace left
+    pub fn end(&mut self) -> PResult<()> {
+        self.whitespace();
+
+        match self.rest.is_empty() {
+            true => Ok(()),
+            false => Err(self.error("Unexpected text")),
+        }
+    }
+}
+
+fn number<T: FromStr>(digits: &str, parser: &Parser) -> PResult<T> {
+    if digits.is_empty() {
+        return Err(parser.error("Expected a number"));
+    }
+
+    digits
+        .parse()
+        .map_err(|_| ParseError::new("The number is out of range", digits))
+}
+
+// Every integer in `s`, with their signs, ignoring the text around them
+pub fn integers<T: FromStr>(s: &str) -> PResult<Vec<T>> {
+    let mut parser = Parser::new(s);
+    let mut integers = vec![];
+
+    loop {
+        let start = parser.rest.find(|c: char| c.is_ascii_digit()).map(|idx| {
+            match parser.rest[..idx].ends_with('-') {
+                true => idx - 1,
+                false => idx,
+            }
+        });
+
+        let Some(start) = start else {
+            return Ok(integers);
+        };
+
+        parser.rest = &parser.rest[start..];
+        integers.push(parser.signed()?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sequences_of_tokens() {
+        let input = "Monkey 3:\n  Starting items: 79, -98\n  Operation: new = old * 19";
+
+        let (id, items, op, n) = Parser::parse_all(input, |p| {
+            let id = p.field("Monkey", Parser::unsigned::<u8>)?;
+            p.literal(":")?;
+            let items = p.field("Starting items:", |p| p.list(",", Parser::signed::<i32>))?;
+            p.literal("Operation: new =")?;
+            let op = p.field("old", |p| p.one_of(&[("*", '*'), ("+", '+')]))?;
+            let n = p.unsigned::<u32>()?;
+
+            Ok((id, items, op, n))
+        })
+        .unwrap();
+
+        assert_eq!((id, items, op, n), (3, vec![79, -98], '*', 19));
+    }
+
+    #[test]
+    fn errors_point_at_the_unexpected_text() {
+        let input = "Valve AA has flow rate=x";
+        let err = Parser::parse_all(input, |p| {
+            p.field("Valve", Parser::word)?;
+            p.field("has flow rate=", Parser::unsigned::<u32>)
+        })
+        .unwrap_err()
+        .locate(input);
+
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 24: Expected a number (found `x`)"
+        );
+
+        let err = Parser::parse_all("1 2", Parser::unsigned::<u8>).unwrap_err();
+        assert_eq!(err.to_string(), "Unexpected text (found `2`)");
+
+        let err = Parser::new("300").unsigned::<u8>().unwrap_err();
+        assert_eq!(err.to_string(), "The number is out of range (found `300`)");
+
+        let err = Parser::new("/").one_of(&[("*", 0), ("+", 1)]).unwrap_err();
+        assert_eq!(err.to_string(), "Expected one of `*`, `+` (found `/`)");
+    }
+
+    #[test]
+    fn extracts_all_integers() {
+        assert_eq!(
+            integers::<i32>("Sensor at x=2, y=-18: beacon at x=-2, y=15"),
+            Ok(vec![2, -18, -2, 15])
+        );
+        assert_eq!(integers::<u8>("a-b"), Ok(vec![]));
+        assert!(integers::<u8>("x=1000").is_err());
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+// A path found by a search, with every node from the source to the goal, both included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+}
+
+// The nodes reached so far and the node each one was reached from. Nodes get an index, so
+// they only need to be hashable and the parents don't have to clone them.
+struct Visited<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> {
+    fn new() -> Self {
+        Self {
+            nodes: vec![],
+            index: HashMap::new(),
+            parents: vec![],
+        }
+    }
+
+    fn get(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    fn insert(&mut self, node: N, parent: Option<usize>) -> usize {
+        let idx = self.nodes.len();
+        self.index.insert(node.clone(), idx);
+        self.nodes.push(node);
+        self.parents.push(parent);
+        idx
+    }
+
+    fn path(&self, mut idx: usize) -> Vec<N> {
+        let mut nodes = vec![self.nodes[idx].clone()];
+
+        while let Some(parent) = self.parents[idx] {
+            nodes.push(self.nodes[parent].clone());
+            idx = parent;
+        }
+
+        nodes.reverse();
+        nodes
+    }
+}
+
+// Breadth-first search from any of `sources` to the closest node that `is_goal`, where every
+// step costs 1
+pub fn bfs<N, I>(
+    sources: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+
+    for source in sources {
+        if visited.get(&source).is_none() {
+            queue.push_back((visited.insert(source, None), 0));
+        }
+    }
+
+    while let Some((idx, cost)) = queue.pop_front() {
+        if is_goal(&visited.nodes[idx]) {
+            return Some(Path {
+                cost,
+                nodes: visited.path(idx),
+            });
+        }
+
+        for next in neighbors(&visited.nodes[idx]) {
+            if visited.get(&next).is_none() {
+                queue.push_back((visited.insert(next, Some(idx)), cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+// The number of steps from the closest of `sources` to every node that can be reached
+pub fn distances<N, I>(
+    sources: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for source in sources {
+        if !distances.contains_key(&source) {
+            distances.insert(source.clone(), 0);
+            queue.push_back((source, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+// Dijkstra's search, for steps with different non-negative costs
+pub fn dijkstra<N, C, I>(
+    sources: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(sources, neighbors, |_| C::default(), is_goal)
+}
+
+// A* search. The path is the cheapest one as long as `heuristic` never overestimates the cost
+// left to reach a goal.
+pub fn astar<N, C, I>(
+    sources: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut visited = Visited::new();
+    let mut costs = vec![];
+    let mut queue = BinaryHeap::new();
+
+    for source in sources {
+        if visited.get(&source).is_none() {
+            let estimate = heuristic(&source);
+            queue.push(Reverse((
+                estimate,
+                C::default(),
+                visited.insert(source, None),
+            )));
+            costs.push(C::default());
+        }
+    }
+
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        // Nodes are queued again when a cheaper way to them is found
+        if cost > costs[idx] {
+            continue;
+        }
+
+        if is_goal(&visited.nodes[idx]) {
+            return Some(Path {
+                cost,
+                nodes: visited.path(idx),
+            });
+        }
+
+        for (next, step) in neighbors(&visited.nodes[idx]) {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic(&next);
+
+            let next_idx = match visited.get(&next) {
+                Some(next_idx) if costs[next_idx] <= next_cost => continue,
+                Some(next_idx) => {
+                    costs[next_idx] = next_cost;
+                    visited.parents[next_idx] = Some(idx);
+                    next_idx
+                }
+                None => {
+                    costs.push(next_cost);
+                    visited.insert(next, Some(idx))
+                }
+            };
+
+            queue.push(Reverse((estimate, next_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A line of nodes 0..=10 where each node goes to the next one for 1, and to the one after
+    // it for 3
+    fn line(node: &u32) -> Vec<(u32, u32)> {
+        [(node + 1, 1), (node + 2, 3)]
+            .into_iter()
+            .filter(|(next, _)| *next <= 10)
+            .collect()
+    }
+
+    #[test]
+    fn bfs_finds_the_fewest_steps() {
+        let path = bfs(
+            [0],
+            |node| line(node).into_iter().map(|(next, _)| next),
+            |node| *node == 6,
+        );
+
+        assert_eq!(
+            path,
+            Some(Path {
+                cost: 3,
+                nodes: vec![0, 2, 4, 6]
+            })
+        );
+        assert_eq!(bfs([0], |_| [], |node| *node == 6), None);
+        assert_eq!(bfs([4, 1], |_| [], |node| *node == 1).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn distances_from_several_sources() {
+        let distances = distances([0, 8], |node: &u32| {
+            [node.wrapping_sub(1), node + 1]
+                .into_iter()
+                .filter(|next| *next <= 10)
+        });
+
+        assert_eq!(distances.len(), 11);
+        assert_eq!(distances[&4], 4);
+        assert_eq!(distances[&6], 2);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_find_the_cheapest_path() {
+        let expected = Path {
+            cost: 6,
+            nodes: vec![0, 1, 2, 3, 4, 5, 6],
+        };
+
+        assert_eq!(
+            dijkstra([0], line, |node| *node == 6),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            astar(
+                [0],
+                line,
+                |node| 6_u32.saturating_sub(*node),
+                |node| *node == 6
+            ),
+            Some(expected)
+        );
+        assert_eq!(dijkstra([0], line, |node| *node == 11), None);
+    }
+}
@@ -0,0 +1,429 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    error::ParseError,
+    runner::{solve_day, Day, Part, Solved},
+    solution::Answer,
+    utils::{
+        color::{paint, Color},
+        input::{InputSource, CRATE_ROOT},
+    },
+};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+// The expected answers, by day and part, as they are written in `answers.toml`:
+//
+//     [10]
+//     1 = 13140
+//     2 = "##..##..\n###...##"
+//
+// Only this subset of TOML is supported: tables named after days, keys named after parts and
+// integer or string values.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, u8), String>);
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, error: io::Error },
+    Parse { path: PathBuf, error: ParseError },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io { path, error } => {
+                write!(f, "Can't access {}: {error}", path.display())
+            }
+            AnswersError::Parse { path, error } => write!(f, "{}: {error}", path.display()),
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+impl Answers {
+    // `answers.toml` in the working directory when there is one, or in the crate root
+    pub fn path() -> PathBuf {
+        let local = env::current_dir()
+            .map(|dir| dir.join(ANSWERS_FILE))
+            .unwrap_or_else(|_| PathBuf::from(ANSWERS_FILE));
+
+        match local.is_file() {
+            true => local,
+            false => Path::new(CRATE_ROOT).join(ANSWERS_FILE),
+        }
+    }
+
+    // Reads the answers in `path`, which are empty when the file doesn't exist yet
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse().map_err(|error| AnswersError::Parse {
+                path: path.to_path_buf(),
+                error,
+            }),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(AnswersError::Io {
+                path: path.to_path_buf(),
+                error,
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        fs::write(path, self.to_string()).map_err(|error| AnswersError::Io {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part.number())).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: &Answer) {
+        self.0.insert((day, part.number()), answer.to_string());
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_toml(s).map_err(|err| err.locate(s))
+    }
+}
+
+impl Answers {
+    fn parse_toml(s: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for line in s.lines() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let name = table
+                    .split('#')
+                    .next()
+                    .unwrap_or_default()
+                    .trim_end()
+                    .strip_suffix(']')
+                    .ok_or_else(|| ParseError::new("Expected a table like `[12]`", line))?;
+                day = Some(parse_key::<u8>(name, "Expected a day")?);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::new("Expected `part = answer`", line))?;
+            let day =
+                day.ok_or_else(|| ParseError::new("Answers must be inside a day table", line))?;
+
+            let part = parse_key::<u8>(key, "Expected part 1 or 2")?;
+            if !(1..=2).contains(&part) {
+                return Err(ParseError::new("Expected part 1 or 2", key.trim()));
+            }
+
+            answers.insert((day, part), parse_value(value.trim())?);
+        }
+
+        Ok(Self(answers))
+    }
+}
+
+fn parse_key<T: FromStr>(key: &str, message: &str) -> Result<T, ParseError> {
+    let key = key.trim();
+    let bare = key.trim_matches('"');
+
+    bare.parse().map_err(|_| ParseError::new(message, key))
+}
+
+// Parses an integer or a basic string, ignoring a trailing comment
+fn parse_value(value: &str) -> Result<String, ParseError> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        let number = value.split('#').next().unwrap_or_default().trim();
+        return match number.replace('_', "").parse::<i64>() {
+            Ok(number) => Ok(number.to_string()),
+            Err(_) => Err(ParseError::new("Expected a number or a string", number)),
+        };
+    };
+
+    let mut result = String::new();
+    let mut chars = quoted.char_indices();
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '"' => {
+                let rest = quoted[idx + 1..].trim();
+                if !rest.is_empty() && !rest.starts_with('#') {
+                    return Err(ParseError::new("Unexpected text after the answer", rest));
+                }
+                return Ok(result);
+            }
+            '\\' => match chars.next() {
+                Some((_, 'n')) => result.push('\n'),
+                Some((_, 't')) => result.push('\t'),
+                Some((_, '"')) => result.push('"'),
+                Some((_, '\\')) => result.push('\\'),
+                _ => return Err(ParseError::new("Unknown escape sequence", &quoted[idx..])),
+            },
+            c => result.push(c),
+        }
+    }
+
+    Err(ParseError::new("Unterminated string", value))
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# Recorded with `aoc verify --record`")?;
+
+        let mut day = None;
+        for ((number, part), answer) in self.0.iter() {
+            if day != Some(number) {
+                writeln!(f, "\n[{number}]")?;
+                day = Some(number);
+            }
+
+            // Text like `007` stays a string, it wouldn't read back the same as a number
+            match answer.parse::<i64>() {
+                Ok(number) if number.to_string() == *answer => writeln!(f, "{part} = {answer}")?,
+                _ => {
+                    let escaped = answer
+                        .replace('\\', "\\\\")
+                        .replace('"', "\\\"")
+                        .replace('\n', "\\n")
+                        .replace('\t', "\\t");
+                    writeln!(f, "{part} = \"{escaped}\"")?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Missing,
+}
+
+// The outcome of both parts of a day
+pub struct DayReport {
+    pub number: u8,
+    pub statuses: [Status; 2],
+    pub answers: Vec<Answer>,
+}
+
+// Solves the days and compares their answers with the expected ones
+pub fn check(days: &[&Day], expected: &Answers, source: &InputSource) -> Vec<DayReport> {
+    days.iter()
+        .map(|day| {
+            let number = day.number;
+
+            match solve_day(day, &Part::ALL, source) {
+                Ok(Solved { answers, .. }) => DayReport {
+                    number,
+                    statuses: [0, 1]
+                        .map(|idx| compare(expected.get(number, Part::ALL[idx]), &answers[idx])),
+                    answers,
+                },
+                Err(err) => DayReport {
+                    number,
+                    statuses: [0, 1].map(|_| Status::Fail(err.to_string())),
+                    answers: vec![],
+                },
+            }
+        })
+        .collect()
+}
+
+fn compare(expected: Option<&str>, answer: &Answer) -> Status {
+    match (expected, answer) {
+        (None, _) => Status::Missing,
+        (Some(expected), Answer::Unimplemented) => {
+            Status::Fail(format!("expected `{expected}`, but it's unimplemented"))
+        }
+        (Some(expected), answer) if answer.to_string() == expected => Status::Pass,
+        (Some(expected), answer) => Status::Fail(format!("expected `{expected}`, got `{answer}`")),
+    }
+}
+
+// Runs `aoc verify`: prints how every part compares to `answers.toml` and, when recording,
+// writes the new answers into it. Returns the number of parts that failed.
+pub fn verify(days: &[&Day], record: bool) -> Result<usize, AnswersError> {
+    let path = Answers::path();
+    let mut answers = Answers::load(&path)?;
+
+    let reports = check(days, &answers, &InputSource::Default);
+    print_table(&reports);
+
+    let failures = reports
+        .iter()
+        .flat_map(|report| report.statuses.iter())
+        .filter(|status| matches!(status, Status::Fail(_)))
+        .count();
+
+    if record {
+        let mut recorded = 0;
+        for report in reports.iter() {
+            for (part, answer) in Part::ALL.iter().zip(report.answers.iter()) {
+                if answer != &Answer::Unimplemented {
+                    answers.insert(report.number, *part, answer);
+                    recorded += 1;
+                }
+            }
+        }
+
+        answers.save(&path)?;
+        println!("\nRecorded {recorded} answer(s) in {}", path.display());
+    }
+
+    Ok(failures)
+}
+
+fn print_table(reports: &[DayReport]) {
+    let mut totals = [0; 3];
+
+    println!("Day  Part 1   Part 2");
+    for report in reports.iter() {
+        let cells = report
+            .statuses
+            .iter()
+            .map(|status| {
+                let (label, color, total) = match status {
+                    Status::Pass => ("pass", Color::Green, 0),
+                    Status::Fail(_) => ("fail", Color::Red, 1),
+                    Status::Missing => ("missing", Color::Yellow, 2),
+                };
+                totals[total] += 1;
+
+                paint(&format!("{label:<7}"), color)
+            })
+            .collect::<Vec<_>>();
+
+        let row = format!("{:>3}  {}  {}", report.number, cells[0], cells[1]);
+        println!("{}", row.trim_end());
+    }
+
+    let mut details = reports.iter().flat_map(|report| {
+        Part::ALL.iter().zip(report.statuses.iter()).filter_map(
+            move |(part, status)| match status {
+                Status::Fail(reason) => {
+                    Some(format!("Day {} part {part}: {reason}", report.number))
+                }
+                _ => None,
+            },
+        )
+    });
+
+    if let Some(first) = details.next() {
+        println!("\n{first}");
+        details.for_each(|detail| println!("{detail}"));
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        totals[0], totals[1], totals[2]
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_writes_answers() {
+        let content = "# Recorded with `aoc verify --record`\n\n\
+                       [5]\n1 = \"CMZ\"\n2 = \"MCD\"\n\n\
+                       [10]\n1 = 13140\n2 = \"##..\\n###.\"\n";
+
+        let answers = content.parse::<Answers>().unwrap();
+
+        assert_eq!(answers.get(5, Part::One), Some("CMZ"));
+        assert_eq!(answers.get(10, Part::One), Some("13140"));
+        assert_eq!(answers.get(10, Part::Two), Some("##..\n###."));
+        assert_eq!(answers.get(11, Part::One), None);
+        assert_eq!(answers.to_string(), content);
+    }
+
+    #[test]
+    fn accepts_comments_and_quoted_keys() {
+        let answers = "[\"1\"] # Calorie Counting\n\"1\" = 24_000 # example\n"
+            .parse::<Answers>()
+            .unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some("24000"));
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        let err = "[1]\n3 = 10\n".parse::<Answers>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: Expected part 1 or 2 (found `3`)"
+        );
+
+        assert!("1 = 10\n".parse::<Answers>().is_err());
+        assert!("[1]\n1 = \"10\n".parse::<Answers>().is_err());
+        assert!("[1]\n1 = ten\n".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(compare(Some("10"), &Answer::Int(10)), Status::Pass);
+        assert_eq!(compare(None, &Answer::Int(10)), Status::Missing);
+        assert_eq!(compare(None, &Answer::Unimplemented), Status::Missing);
+        assert!(matches!(
+            compare(Some("10"), &Answer::Int(11)),
+            Status::Fail(_)
+        ));
+        assert!(matches!(
+            compare(Some("10"), &Answer::Unimplemented),
+            Status::Fail(_)
+        ));
+    }
+
+    #[test]
+    #[ignore = "needs the real inputs, run with `cargo test --release -- --ignored`"]
+    fn real_inputs() {
+        let answers = Answers::load(&Answers::path()).unwrap();
+        let days = crate::runner::DAYS.iter().collect::<Vec<_>>();
+
+        let failures = check(&days, &answers, &InputSource::Default)
+            .into_iter()
+            .flat_map(|report| {
+                Part::ALL
+                    .into_iter()
+                    .zip(report.statuses)
+                    .filter_map(move |(part, status)| match status {
+                        Status::Fail(reason) => {
+                            Some(format!("day {} part {part}: {reason}", report.number))
+                        }
+                        _ => None,
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        assert!(
+            failures.is_empty(),
+            "Wrong answers:\n{}",
+            failures.join("\n")
+        );
+    }
+}
@@ -0,0 +1,36 @@
+// The days can be used from other crates, not only through the `aoc` binary
+use advent_of_code_2022::{
+    day13::{Packet, PacketItem},
+    day21::BinaryTree,
+    day4::Day4,
+    runner::{find_day, Part},
+    solution::{Answer, Solution},
+};
+
+#[test]
+fn solutions_can_be_called_directly() {
+    let pairs = Day4::parse("2-4,6-8\n2-8,3-7\n").unwrap();
+
+    assert_eq!(Day4::part1(&pairs), Answer::Int(1));
+}
+
+#[test]
+fn days_can_be_looked_up_in_the_registry() {
+    let day = find_day(1).unwrap();
+    let solved = (day.solve)("1000\n2000\n\n4000\n", &[Part::Two]).unwrap();
+
+    assert_eq!(day.title, "Calorie Counting");
+    assert_eq!(solved.answers, vec![Answer::Int(7000)]);
+}
+
+#[test]
+fn day_types_can_be_reused() {
+    let packet = "[1,[2,3]]".parse::<Packet>().unwrap();
+    let other = "[1,[2,4]]".parse::<Packet>().unwrap();
+    assert!(packet < other);
+    assert!(matches!(packet.data(), PacketItem::Multi(_)));
+
+    let tree = "root: a * b\na: 6\nb: 7\n".parse::<BinaryTree>().unwrap();
+    assert_eq!(tree.reduce(), 42);
+    assert_eq!(tree.root().borrow().id(), "root");
+}